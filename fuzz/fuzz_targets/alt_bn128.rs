@@ -0,0 +1,24 @@
+#![no_main]
+// `alt_bn128_add`, `alt_bn128_mul` and `alt_bn128_pairing` on arbitrary coordinates, and the
+// EIP-196/197 precompiles on arbitrary bytes, which must give the same answer on both backends.
+use casper_groth16::bn::{self, Zeropool};
+use casper_groth16::bn254::Arkworks;
+use casper_groth16::precompile::{ecadd, ecmul, ecpairing};
+use casper_types::U256;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let words: Vec<U256> = data.chunks(32).map(U256::from_big_endian).collect();
+    let word = |i: usize| words.get(i).copied().unwrap_or_default();
+    let _ = bn::alt_bn128_add(word(0), word(1), word(2), word(3));
+    let _ = bn::alt_bn128_mul(word(0), word(1), word(2));
+    let pairs = words
+        .chunks_exact(6)
+        .map(|pair| (pair[0], pair[1], pair[2], pair[3], pair[4], pair[5]))
+        .collect();
+    let _ = bn::alt_bn128_pairing(pairs);
+
+    assert_eq!(ecadd::<Zeropool>(data), ecadd::<Arkworks>(data));
+    assert_eq!(ecmul::<Zeropool>(data), ecmul::<Arkworks>(data));
+    assert_eq!(ecpairing::<Zeropool>(data), ecpairing::<Arkworks>(data));
+});
@@ -0,0 +1,87 @@
+#![no_main]
+// The decoders for untrusted proofs and keys: Casper bytesrepr for the `bn` types, as received by
+// the contract, the gnark, zkey and snarkjs loaders and Solidity calldata, with their conversions
+// into both backends.
+use casper_groth16::gnark::{GnarkProof, GnarkPublicWitness, GnarkVerifyingKey};
+use casper_groth16::snarkjs::{SnarkjsProof, SnarkjsPublicSignals, SnarkjsVerifyingKey};
+use casper_groth16::solidity::SolidityCalldata;
+use casper_groth16::zkey::ZkeyVerifyingKey;
+use casper_groth16::{bn, bn254, VerifyError};
+use casper_types::bytesrepr::{FromBytes, ToBytes};
+use libfuzzer_sys::fuzz_target;
+
+// Zeropool checks the curve equation when it builds a point, arkworks leaves that to verification.
+// Whatever converts on Zeropool must convert on arkworks.
+fn agree<Z, A>(zeropool: Result<Z, VerifyError>, arkworks: Result<A, VerifyError>) {
+    match (zeropool, arkworks) {
+        (Ok(_), arkworks) => assert!(arkworks.is_ok()),
+        (Err(VerifyError::PointNotOnCurve), _) => {}
+        (zeropool, arkworks) => assert_eq!(zeropool.err(), arkworks.err()),
+    }
+}
+
+// A value that decodes must encode back to the bytes it was read from
+fn round_trip<T: FromBytes + ToBytes>(data: &[u8]) {
+    if let Ok((value, remainder)) = T::from_bytes(data) {
+        let read = &data[..data.len() - remainder.len()];
+        assert_eq!(value.to_bytes().unwrap(), read);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    round_trip::<bn::Proof>(data);
+    round_trip::<bn::VerifyingKey>(data);
+    // `U256` accepts non-minimal encodings, only the value has to survive
+    if let Ok((inputs, _)) = bn::PublicInputs::from_bytes(data) {
+        let (decoded, _) = bn::PublicInputs::from_bytes(&inputs.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, inputs);
+    }
+
+    // The backends decompress points and leave Montgomery form on their own field arithmetic
+    if let Ok(vk) = GnarkVerifyingKey::from_bytes(data) {
+        let zeropool: Result<bn::VerifyingKey, VerifyError> = (&vk).try_into();
+        let arkworks: Result<bn254::VerifyingKey, VerifyError> = (&vk).try_into();
+        agree(zeropool, arkworks);
+    }
+    if let Ok(proof) = GnarkProof::from_bytes(data) {
+        let zeropool: Result<bn::Proof, VerifyError> = (&proof).try_into();
+        let arkworks: Result<bn254::Proof, VerifyError> = (&proof).try_into();
+        agree(zeropool, arkworks);
+    }
+    if let Ok(witness) = GnarkPublicWitness::from_bytes(data) {
+        let _ = bn::PublicInputs::from(&witness);
+        let _ = bn254::PublicInputs::from(&witness);
+    }
+    if let Ok(vk) = ZkeyVerifyingKey::from_bytes(data) {
+        let zeropool: Result<bn::VerifyingKey, VerifyError> = (&vk).try_into();
+        let arkworks: Result<bn254::VerifyingKey, VerifyError> = (&vk).try_into();
+        agree(zeropool, arkworks);
+    }
+    // Calldata has a single encoding
+    if let Ok(calldata) = SolidityCalldata::from_bytes(data) {
+        assert_eq!(calldata.to_bytes(), data);
+        let zeropool: Result<bn::Proof, VerifyError> = (&calldata.proof).try_into();
+        let arkworks: Result<bn254::Proof, VerifyError> = (&calldata.proof).try_into();
+        agree(zeropool, arkworks);
+    }
+
+    let Ok(json) = core::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(calldata) = SolidityCalldata::from_generatecall(json) {
+        let text = calldata.to_generatecall();
+        assert_eq!(SolidityCalldata::from_generatecall(&text), Ok(calldata));
+    }
+    if let Ok(vk) = SnarkjsVerifyingKey::from_json(json) {
+        let _: Result<bn::VerifyingKey, VerifyError> = (&vk).try_into();
+        let _: Result<bn254::VerifyingKey, VerifyError> = (&vk).try_into();
+    }
+    if let Ok(proof) = SnarkjsProof::from_json(json) {
+        let _: Result<bn::Proof, VerifyError> = (&proof).try_into();
+        let _: Result<bn254::Proof, VerifyError> = (&proof).try_into();
+    }
+    if let Ok(public) = SnarkjsPublicSignals::from_json(json) {
+        let _: Result<bn::PublicInputs, VerifyError> = (&public).try_into();
+        let _: Result<bn254::PublicInputs, VerifyError> = (&public).try_into();
+    }
+});
@@ -0,0 +1,105 @@
+#![no_main]
+// `bn::verify` and `bn254::verify` on a verifying key, proof and public inputs read from arbitrary
+// bytes. Both must return the same `Ok(bool)` or `VerifyError`.
+//
+// Layout, zero padded: n = data[0] % 4 || alpha1 || beta2 || gamma2 || delta2 || ic[0..=n]
+// || a || b || c || inputs[0..n], points in the `Bn254Backend` encoding, inputs 32 byte big-endian
+// and unreduced.
+use casper_groth16::bn::{self, Zeropool};
+use casper_groth16::bn254::{self, Arkworks};
+use casper_groth16::groth16::{Bn254Backend, G1_BYTES, G2_BYTES, SCALAR_BYTES};
+use casper_groth16::types::{InputPolicy, Proof, PublicInputs, VerifyOptions, VerifyingKey};
+use casper_groth16::VerifyError;
+use casper_types::U256;
+use libfuzzer_sys::fuzz_target;
+use num_bigint::BigUint;
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        let len = self.data.len().min(N);
+        bytes[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        bytes
+    }
+}
+
+type Encoded = (
+    VerifyingKey<[u8; G1_BYTES], [u8; G2_BYTES]>,
+    Proof<[u8; G1_BYTES], [u8; G2_BYTES]>,
+    Vec<[u8; SCALAR_BYTES]>,
+);
+
+fn read(data: &[u8]) -> Encoded {
+    let mut reader = Reader { data };
+    let n = reader.take::<1>()[0] as usize % 4;
+    let vk = VerifyingKey {
+        alpha1: reader.take(),
+        beta2: reader.take(),
+        gamma2: reader.take(),
+        delta2: reader.take(),
+        ic: (0..=n).map(|_| reader.take()).collect(),
+    };
+    let proof = Proof {
+        a: reader.take(),
+        b: reader.take(),
+        c: reader.take(),
+    };
+    let inputs = (0..n).map(|_| reader.take()).collect();
+    (vk, proof, inputs)
+}
+
+// Decodes with `B`, returning the first encoding error
+#[allow(clippy::type_complexity)]
+fn decode<B: Bn254Backend>(
+    (vk, proof, _): &Encoded,
+) -> Result<(VerifyingKey<B::G1, B::G2>, Proof<B::G1, B::G2>), VerifyError> {
+    Ok((
+        VerifyingKey {
+            alpha1: B::g1_from_bytes(&vk.alpha1)?,
+            beta2: B::g2_from_bytes(&vk.beta2)?,
+            gamma2: B::g2_from_bytes(&vk.gamma2)?,
+            delta2: B::g2_from_bytes(&vk.delta2)?,
+            ic: vk
+                .ic
+                .iter()
+                .map(B::g1_from_bytes)
+                .collect::<Result<_, _>>()?,
+        },
+        Proof {
+            a: B::g1_from_bytes(&proof.a)?,
+            b: B::g2_from_bytes(&proof.b)?,
+            c: B::g1_from_bytes(&proof.c)?,
+        },
+    ))
+}
+
+fn verify_zeropool(encoded: &Encoded, options: &VerifyOptions) -> Result<bool, VerifyError> {
+    let (vk, proof) = decode::<Zeropool>(encoded)?;
+    let inputs = encoded.2.iter().map(|input| U256::from_big_endian(input));
+    bn::verify_with_options(&vk, &proof, &PublicInputs(inputs.collect()), options)
+}
+
+fn verify_arkworks(encoded: &Encoded, options: &VerifyOptions) -> Result<bool, VerifyError> {
+    let (vk, proof) = decode::<Arkworks>(encoded)?;
+    let inputs = encoded.2.iter().map(|input| BigUint::from_bytes_be(input));
+    bn254::verify_with_options(&vk, &proof, &PublicInputs(inputs.collect()), options)
+}
+
+fuzz_target!(|data: &[u8]| {
+    let encoded = read(data);
+    for inputs in [InputPolicy::RejectNonCanonical, InputPolicy::Reduce] {
+        let options = VerifyOptions {
+            inputs,
+            ..Default::default()
+        };
+        assert_eq!(
+            verify_zeropool(&encoded, &options),
+            verify_arkworks(&encoded, &options)
+        );
+    }
+});
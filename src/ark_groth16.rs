@@ -0,0 +1,159 @@
+// Conversions between the verifier types and `ark_groth16::{Proof, VerifyingKey}` over BN254, for
+// proofs made with the arkworks prover. `bn254` types convert in both directions without loss.
+// `bn` points are range checked on the way into arkworks, curve membership is left to
+// verification as for every other loader.
+use ark_bn254::{Bn254, Fr};
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+#[cfg(feature = "zeropool")]
+use crate::{
+    bn::{self, Zeropool},
+    bn254::Arkworks,
+    groth16::Bn254Backend,
+    VerifyError,
+};
+use crate::{bn254, types};
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+
+impl From<&ark_groth16::VerifyingKey<Bn254>> for bn254::VerifyingKey {
+    fn from(vk: &ark_groth16::VerifyingKey<Bn254>) -> Self {
+        types::VerifyingKey {
+            alpha1: vk.alpha_g1,
+            beta2: vk.beta_g2,
+            gamma2: vk.gamma_g2,
+            delta2: vk.delta_g2,
+            ic: vk.gamma_abc_g1.clone(),
+        }
+    }
+}
+
+impl From<&bn254::VerifyingKey> for ark_groth16::VerifyingKey<Bn254> {
+    fn from(vk: &bn254::VerifyingKey) -> Self {
+        ark_groth16::VerifyingKey {
+            alpha_g1: vk.alpha1,
+            beta_g2: vk.beta2,
+            gamma_g2: vk.gamma2,
+            delta_g2: vk.delta2,
+            gamma_abc_g1: vk.ic.clone(),
+        }
+    }
+}
+
+impl From<&ark_groth16::Proof<Bn254>> for bn254::Proof {
+    fn from(proof: &ark_groth16::Proof<Bn254>) -> Self {
+        types::Proof {
+            a: proof.a,
+            b: proof.b,
+            c: proof.c,
+        }
+    }
+}
+
+impl From<&bn254::Proof> for ark_groth16::Proof<Bn254> {
+    fn from(proof: &bn254::Proof) -> Self {
+        ark_groth16::Proof {
+            a: proof.a,
+            b: proof.b,
+            c: proof.c,
+        }
+    }
+}
+
+/// The public inputs passed to `ark_groth16::Groth16::verify_proof`.
+impl From<&[Fr]> for bn254::PublicInputs {
+    fn from(inputs: &[Fr]) -> Self {
+        types::PublicInputs(
+            inputs
+                .iter()
+                .map(|input| BigUint::from(input.into_bigint()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&ark_groth16::VerifyingKey<Bn254>> for bn::VerifyingKey {
+    fn from(vk: &ark_groth16::VerifyingKey<Bn254>) -> Self {
+        types::VerifyingKey {
+            alpha1: bn_g1(&vk.alpha_g1),
+            beta2: bn_g2(&vk.beta_g2),
+            gamma2: bn_g2(&vk.gamma_g2),
+            delta2: bn_g2(&vk.delta_g2),
+            ic: vk.gamma_abc_g1.iter().map(bn_g1).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&bn::VerifyingKey> for ark_groth16::VerifyingKey<Bn254> {
+    type Error = VerifyError;
+
+    fn try_from(vk: &bn::VerifyingKey) -> Result<Self, Self::Error> {
+        Ok(ark_groth16::VerifyingKey {
+            alpha_g1: ark_g1(&vk.alpha1)?,
+            beta_g2: ark_g2(&vk.beta2)?,
+            gamma_g2: ark_g2(&vk.gamma2)?,
+            delta_g2: ark_g2(&vk.delta2)?,
+            gamma_abc_g1: vk.ic.iter().map(ark_g1).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&ark_groth16::Proof<Bn254>> for bn::Proof {
+    fn from(proof: &ark_groth16::Proof<Bn254>) -> Self {
+        types::Proof {
+            a: bn_g1(&proof.a),
+            b: bn_g2(&proof.b),
+            c: bn_g1(&proof.c),
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&bn::Proof> for ark_groth16::Proof<Bn254> {
+    type Error = VerifyError;
+
+    fn try_from(proof: &bn::Proof) -> Result<Self, Self::Error> {
+        Ok(ark_groth16::Proof {
+            a: ark_g1(&proof.a)?,
+            b: ark_g2(&proof.b)?,
+            c: ark_g1(&proof.c)?,
+        })
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&[Fr]> for bn::PublicInputs {
+    fn from(inputs: &[Fr]) -> Self {
+        types::PublicInputs(
+            inputs
+                .iter()
+                .map(|input| U256::from_big_endian(&Arkworks::scalar_to_bytes(input)))
+                .collect(),
+        )
+    }
+}
+
+// Both backends share the byte encoding and arkworks coordinates are always canonical
+#[cfg(feature = "zeropool")]
+fn bn_g1(p: &ark_bn254::G1Affine) -> bn::G1Point {
+    Zeropool::g1_from_bytes(&Arkworks::g1_to_bytes(p)).expect("coordinates are in the base field")
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_g2(p: &ark_bn254::G2Affine) -> bn::G2Point {
+    p.into()
+}
+
+#[cfg(feature = "zeropool")]
+fn ark_g1(p: &bn::G1Point) -> Result<ark_bn254::G1Affine, VerifyError> {
+    Arkworks::g1_from_bytes(&Zeropool::g1_to_bytes(p))
+}
+
+#[cfg(feature = "zeropool")]
+fn ark_g2(p: &bn::G2Point) -> Result<ark_bn254::G2Affine, VerifyError> {
+    p.try_into()
+}
@@ -0,0 +1,65 @@
+// Shared pieces of batch verification: the transcript the random linear combination scalars are
+// drawn from, and the bisection that locates an invalid proof once a batch is rejected.
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use sha2::{Digest, Sha256};
+
+use crate::VerifyError;
+
+const BATCH_DOMAIN: &[u8] = b"casper-groth16-batch-v1";
+
+/// Accumulates the verifying key, proofs and public inputs of a batch. The scalars are only known
+/// once every proof is fixed, so a prover cannot pick proofs whose errors cancel out.
+pub(crate) struct Transcript(Sha256);
+
+impl Transcript {
+    pub(crate) fn new() -> Self {
+        Transcript(Sha256::new_with_prefix(BATCH_DOMAIN))
+    }
+
+    /// Appends a field element or coordinate, encoded as 32 bytes big-endian.
+    pub(crate) fn append(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// Derives `n` 128 bit scalars, big-endian. 128 bits keep the scalar multiplications cheap
+    /// while a forged batch still passes with probability at most 2^-128.
+    pub(crate) fn challenge_scalars(self, n: usize) -> Vec<[u8; 16]> {
+        let seed = self.0.finalize();
+        (0..n as u64)
+            .map(|i| {
+                let digest = Sha256::new_with_prefix(seed)
+                    .chain_update(i.to_be_bytes())
+                    .finalize();
+                let mut scalar = [0u8; 16];
+                scalar.copy_from_slice(&digest[..16]);
+                scalar
+            })
+            .collect()
+    }
+}
+
+/// Returns the index of the first invalid proof in `range`, or `None` if `check` accepts it.
+/// A rejected range is split in halves until a single proof is left. Each proof keeps its scalar
+/// in every sub-batch, so the check of a range is the product of the checks of its halves and one
+/// of them is rejected whenever the range is.
+pub(crate) fn first_invalid<F>(
+    range: Range<usize>,
+    check: &mut F,
+) -> Result<Option<usize>, VerifyError>
+where
+    F: FnMut(Range<usize>) -> Result<bool, VerifyError>,
+{
+    if range.is_empty() || check(range.clone())? {
+        return Ok(None);
+    }
+    if range.len() == 1 {
+        return Ok(Some(range.start));
+    }
+    let mid = range.start + range.len() / 2;
+    match first_invalid(range.start..mid, check)? {
+        Some(index) => Ok(Some(index)),
+        None => first_invalid(mid..range.end, check),
+    }
+}
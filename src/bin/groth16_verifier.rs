@@ -0,0 +1,146 @@
+// Casper contract storing verifying keys by id and verifying proofs against them.
+// Build with
+// `cargo build --release --no-default-features --features casper --target wasm32-unknown-unknown`.
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+#[cfg(target_arch = "wasm32")]
+mod contract {
+    use std::collections::BTreeMap;
+
+    use casper_contract::{
+        contract_api::{runtime, storage},
+        unwrap_or_revert::UnwrapOrRevert,
+    };
+    use casper_groth16::bn::{self, Proof, PublicInputs, VerifyingKey};
+    use casper_types::{
+        contract_messages::{MessagePayload, MessageTopicOperation},
+        contracts::NamedKeys,
+        ApiError, CLType, CLTyped, CLValue, EntityEntryPoint, EntryPointAccess, EntryPointPayment,
+        EntryPointType, EntryPoints, Key, Parameter, URef,
+    };
+
+    const CONTRACT_PACKAGE: &str = "groth16_verifier_package";
+    const CONTRACT_ACCESS: &str = "groth16_verifier_access";
+    const VERIFYING_KEYS: &str = "verifying_keys";
+    const VERIFICATION_TOPIC: &str = "verification";
+
+    const ENTRY_POINT_REGISTER_VK: &str = "register_vk";
+    const ENTRY_POINT_VERIFY: &str = "verify";
+
+    const ARG_ID: &str = "id";
+    const ARG_VK: &str = "vk";
+    const ARG_PROOF: &str = "proof";
+    const ARG_INPUTS: &str = "inputs";
+
+    /// Contract level revert codes, kept clear of the `VerifyError` codes.
+    #[repr(u16)]
+    enum Error {
+        UnknownVerifyingKey = 100,
+        VerifyingKeyExists = 101,
+        MissingVerifyingKeys = 102,
+    }
+
+    impl From<Error> for ApiError {
+        fn from(err: Error) -> Self {
+            ApiError::User(err as u16)
+        }
+    }
+
+    fn verifying_keys() -> URef {
+        runtime::get_key(VERIFYING_KEYS)
+            .and_then(Key::into_uref)
+            .unwrap_or_revert_with(Error::MissingVerifyingKeys)
+    }
+
+    // Open to every caller and first come, first served, an id proves nothing about its key unless
+    // the id is derived from the key. Malformed keys revert with their `VerifyError` code instead
+    // of taking the id.
+    #[no_mangle]
+    pub extern "C" fn register_vk() {
+        let id: String = runtime::get_named_arg(ARG_ID);
+        let vk: VerifyingKey = runtime::get_named_arg(ARG_VK);
+        bn::validate_vk(&vk).unwrap_or_revert();
+
+        let dictionary = verifying_keys();
+        let existing: Option<VerifyingKey> =
+            storage::dictionary_get(dictionary, &id).unwrap_or_revert();
+        if existing.is_some() {
+            runtime::revert(Error::VerifyingKeyExists);
+        }
+        storage::dictionary_put(dictionary, &id, vk);
+    }
+
+    #[no_mangle]
+    pub extern "C" fn verify() {
+        let id: String = runtime::get_named_arg(ARG_ID);
+        let proof: Proof = runtime::get_named_arg(ARG_PROOF);
+        let inputs: PublicInputs = runtime::get_named_arg(ARG_INPUTS);
+
+        let vk: VerifyingKey = storage::dictionary_get(verifying_keys(), &id)
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(Error::UnknownVerifyingKey);
+
+        let valid = bn::verify(&vk, &proof, &inputs).unwrap_or_revert();
+
+        runtime::emit_message(
+            VERIFICATION_TOPIC,
+            &MessagePayload::String(format!("{}:{}", id, valid)),
+        )
+        .unwrap_or_revert();
+        runtime::ret(CLValue::from_t(valid).unwrap_or_revert());
+    }
+
+    fn entry_points() -> EntryPoints {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntityEntryPoint::new(
+            ENTRY_POINT_REGISTER_VK,
+            vec![
+                Parameter::new(ARG_ID, String::cl_type()),
+                Parameter::new(ARG_VK, VerifyingKey::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::Caller,
+        ));
+        entry_points.add_entry_point(EntityEntryPoint::new(
+            ENTRY_POINT_VERIFY,
+            vec![
+                Parameter::new(ARG_ID, String::cl_type()),
+                Parameter::new(ARG_PROOF, Proof::cl_type()),
+                Parameter::new(ARG_INPUTS, PublicInputs::cl_type()),
+            ],
+            CLType::Bool,
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::Caller,
+        ));
+        entry_points
+    }
+
+    #[no_mangle]
+    pub extern "C" fn call() {
+        // the dictionary is created in the installing account's context and moved to the contract
+        let verifying_keys = storage::new_dictionary(VERIFYING_KEYS).unwrap_or_revert();
+        runtime::remove_key(VERIFYING_KEYS);
+
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(VERIFYING_KEYS.to_string(), verifying_keys.into());
+
+        let mut message_topics = BTreeMap::new();
+        message_topics.insert(VERIFICATION_TOPIC.to_string(), MessageTopicOperation::Add);
+
+        storage::new_contract(
+            entry_points(),
+            Some(named_keys),
+            Some(CONTRACT_PACKAGE.to_string()),
+            Some(CONTRACT_ACCESS.to_string()),
+            Some(message_topics),
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("groth16_verifier is a Casper contract, build it for wasm32-unknown-unknown");
+}
@@ -1,137 +1,637 @@
 // Written for the Casper Blockchain with casper_types 5
-use crate::BASE_FIELD_MODULUS;
-use bn::{AffineG1, Fq, Fr, Group, G1};
-#[cfg(feature = "casper")]
-use casper_contract::contract_api::builtins::altbn128::{
-    alt_bn128_add, alt_bn128_mul, alt_bn128_pairing, Fq, Pair, G1,
+use alloc::vec::Vec;
+
+#[cfg(feature = "ark")]
+use crate::bn254::Arkworks;
+use crate::{
+    groth16::{self, Bn254Backend, G1_BYTES, G2_BYTES, SCALAR_BYTES},
+    types::{self, InputPolicy, VerifyOptions},
+    VerifyError,
 };
+#[cfg(not(feature = "casper"))]
+use bn::Gt;
+use bn::{AffineG1, AffineG2, Fq, Fq2, Fr, Group, GroupError, G1, G2};
+#[cfg(feature = "casper")]
+use casper_contract::contract_api::builtins::altbn128;
 use casper_types::U256;
 
-pub fn compute_vk(ics: Vec<AffineG1>, inputs: Vec<U256>) -> (U256, U256) {
-    let mut vk: AffineG1 = ics[0];
-    for (idx, ic) in ics.into_iter().enumerate().skip(1) {
-        let ic_scalar: (U256, U256) = alt_bn128_mul(
-            fq_to_u256(ic.x()),
-            fq_to_u256(ic.y()),
-            inputs[idx - 1].clone(),
-        );
-        println!("processed input: {}, with ic: {}", inputs[idx - 1], idx);
-        let coords = alt_bn128_add(
-            fq_to_u256(vk.x()),
-            fq_to_u256(vk.y()),
-            ic_scalar.0,
-            ic_scalar.1,
-        );
-        vk = AffineG1::new(fq_from_u256(coords.0), fq_from_u256(coords.1)).unwrap();
-    }
-    (fq_to_u256(vk.x()), fq_to_u256(vk.y()))
-}
-
-pub fn negate_g1_affine(x: U256, y: U256) -> (U256, U256) {
-    let base_field_modulus_biguint = U256::from_str_radix(BASE_FIELD_MODULUS, 10).unwrap();
+// BASE_FIELD_MODULUS as little-endian limbs
+const BASE_FIELD_MODULUS_U256: U256 = U256([
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+// Order of G1 and G2 as little-endian limbs
+const SCALAR_FIELD_MODULUS_U256: U256 = U256([
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+pub type Proof = types::Proof<G1Point, G2Point>;
+pub type VerifyingKey = types::VerifyingKey<G1Point, G2Point>;
+pub type PublicInputs = types::PublicInputs<U256>;
+pub type CommittedVerifyingKey = types::CommittedVerifyingKey<G1Point, G2Point>;
+pub type CommittedProof = types::CommittedProof<G1Point, G2Point>;
+
+/// Affine G1 point, `(0, 0)` encodes the point at infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G1Point {
+    pub x: U256,
+    pub y: U256,
+}
+
+/// Affine G2 point, each coordinate held as Fq2 coefficients `[c0, c1]`, i.e. `c0 + c1 * u`.
+/// `(0, 0)` encodes the point at infinity.
+///
+/// Tools disagree on the coefficient order, build points with the constructor named after the
+/// source: `from_snarkjs` for snarkjs JSON and the Casper `altbn128` host functions, `from_eip197`
+/// for the Ethereum precompile and Solidity verifiers, `From<&ark_bn254::G2Affine>` for arkworks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G2Point {
+    pub x: [U256; 2],
+    pub y: [U256; 2],
+}
+
+impl G1Point {
+    pub fn new(x: U256, y: U256) -> Self {
+        G1Point { x, y }
+    }
+}
+
+impl G2Point {
+    /// Coefficients ordered `[c0, c1]`, as `from_snarkjs`.
+    pub fn new(x: [U256; 2], y: [U256; 2]) -> Self {
+        G2Point { x, y }
+    }
+
+    /// Coefficients ordered `[c0, c1]`, as in the `pi_b` and `vk_*_2` arrays of snarkjs JSON and
+    /// in `(bax, bay)`, `(bbx, bby)` of the `altbn128` host functions.
+    pub fn from_snarkjs(x: [U256; 2], y: [U256; 2]) -> Self {
+        G2Point { x, y }
+    }
+
+    /// Coefficients ordered `[c1, c0]`, as in EIP-197 `ECPAIRING` input and the `uint256[2][2]`
+    /// argument of Solidity verifiers.
+    pub fn from_eip197(x: [U256; 2], y: [U256; 2]) -> Self {
+        G2Point {
+            x: [x[1], x[0]],
+            y: [y[1], y[0]],
+        }
+    }
+
+    /// `(x, y)` with coefficients ordered `[c0, c1]`.
+    pub fn to_snarkjs(&self) -> ([U256; 2], [U256; 2]) {
+        (self.x, self.y)
+    }
+
+    /// `(x, y)` with coefficients ordered `[c1, c0]`.
+    pub fn to_eip197(&self) -> ([U256; 2], [U256; 2]) {
+        ([self.x[1], self.x[0]], [self.y[1], self.y[0]])
+    }
+}
+
+/// arkworks coordinates are always canonical, the conversion cannot fail.
+#[cfg(feature = "ark")]
+impl From<&ark_bn254::G2Affine> for G2Point {
+    fn from(p: &ark_bn254::G2Affine) -> Self {
+        g2_from_bytes(&Arkworks::g2_to_bytes(p)).expect("coordinates are in the base field")
+    }
+}
+
+/// Rejects coordinates outside the base field, curve membership is left to verification.
+#[cfg(feature = "ark")]
+impl TryFrom<&G2Point> for ark_bn254::G2Affine {
+    type Error = VerifyError;
+
+    fn try_from(p: &G2Point) -> Result<Self, Self::Error> {
+        Arkworks::g2_from_bytes(&g2_to_bytes(p))
+    }
+}
+
+/// Zeropool bn in software.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Zeropool;
+
+/// The Casper `alt_bn128` host functions.
+#[cfg(feature = "casper")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CasperHost;
+
+// The backend behind `verify` and the `alt_bn128_*` functions.
+#[cfg(feature = "casper")]
+type Backend = CasperHost;
+#[cfg(not(feature = "casper"))]
+type Backend = Zeropool;
+
+pub fn compute_vk(ics: Vec<AffineG1>, inputs: Vec<U256>) -> Result<(U256, U256), VerifyError> {
+    let ic: Vec<G1Point> = ics
+        .iter()
+        .map(|ic| G1Point::new(fq_to_u256(ic.x()), fq_to_u256(ic.y())))
+        .collect();
+    let inputs = inputs
+        .into_iter()
+        .map(fr_from_u256)
+        .collect::<Result<Vec<_>, _>>()?;
+    let vk_x = groth16::compute_vk_x::<Backend>(&ic, &inputs)?;
+    Ok((vk_x.x, vk_x.y))
+}
+
+pub fn negate_g1_affine(x: U256, y: U256) -> Result<(U256, U256), VerifyError> {
+    check_coordinates(&[x, y])?;
     if y == U256::zero() && x == U256::zero() {
-        (x, y)
+        Ok((x, y))
     } else {
-        let neg_y_coord = (base_field_modulus_biguint.clone() - y) % base_field_modulus_biguint;
-        (x, neg_y_coord)
+        let neg_y_coord = (BASE_FIELD_MODULUS_U256 - y) % BASE_FIELD_MODULUS_U256;
+        Ok((x, neg_y_coord))
     }
 }
 
-fn point_from_coords(x: U256, y: U256) -> G1 {
-    let px = Fq::from_slice(&x.to_be_bytes()).unwrap();
-    let py = Fq::from_slice(&y.to_be_bytes()).unwrap();
+fn point_from_coords(x: U256, y: U256) -> Result<G1, VerifyError> {
+    let px = fq_from_u256(x)?;
+    let py = fq_from_u256(y)?;
 
     if px == Fq::zero() && py == Fq::zero() {
-        G1::zero()
+        Ok(G1::zero())
+    } else {
+        affine_from_fq(px, py).map(Into::into)
+    }
+}
+
+fn g2_point_from_coords(x: [U256; 2], y: [U256; 2]) -> Result<G2, VerifyError> {
+    let px = Fq2::new(fq_from_u256(x[0])?, fq_from_u256(x[1])?);
+    let py = Fq2::new(fq_from_u256(y[0])?, fq_from_u256(y[1])?);
+
+    if px.is_zero() && py.is_zero() {
+        Ok(G2::zero())
     } else {
-        AffineG1::new(px, py).unwrap().into()
+        Ok(AffineG2::new(px, py).map_err(group_error)?.into())
+    }
+}
+
+fn point_from_jacobian(p: G1) -> G1Point {
+    match AffineG1::from_jacobian(p) {
+        Some(p) => G1Point::new(fq_to_u256(p.x()), fq_to_u256(p.y())),
+        None => G1Point::new(U256::zero(), U256::zero()),
+    }
+}
+
+fn affine_from_fq(x: Fq, y: Fq) -> Result<AffineG1, VerifyError> {
+    AffineG1::new(x, y).map_err(group_error)
+}
+
+fn group_error(err: GroupError) -> VerifyError {
+    match err {
+        GroupError::NotOnCurve => VerifyError::PointNotOnCurve,
+        GroupError::NotInSubgroup => VerifyError::PointNotInSubgroup,
     }
 }
 
 pub fn fq_to_u256(fq: Fq) -> U256 {
     let mut buf = [0u8; 32];
-    fq.to_big_endian(&mut buf).unwrap();
+    fq.to_big_endian(&mut buf)
+        .expect("buffer holds exactly one field element");
     U256::from_big_endian(&buf)
 }
 
-pub fn fq_from_u256(u256: U256) -> Fq {
+pub fn fq_from_u256(u256: U256) -> Result<Fq, VerifyError> {
     let mut buf = [0u8; 32];
     u256.to_big_endian(&mut buf);
-    let reconstructed = U256::from_be_bytes(buf.clone());
-    assert_eq!(reconstructed, u256);
+    Fq::from_slice(&buf).map_err(|_| VerifyError::CoordinateNotInField)
+}
+
+fn fr_from_u256(value: U256) -> Result<Fr, VerifyError> {
+    if value >= SCALAR_FIELD_MODULUS_U256 {
+        return Err(VerifyError::ScalarOutOfRange);
+    }
+    let mut buf = [0u8; 32];
+    value.to_big_endian(&mut buf);
+    Fr::from_slice(&buf).map_err(|_| VerifyError::ScalarOutOfRange)
+}
 
-    let fq = Fq::from_slice(&buf).unwrap();
-    let u256_rec = fq_to_u256(fq);
-    assert_eq!(u256_rec, u256);
+/// The canonical value of a scalar, the integer `alt_bn128_mul` multiplies by. `Fr::to_big_endian`
+/// writes the Montgomery form, this converts out of it first.
+pub fn fr_to_u256(value: Fr) -> U256 {
+    let mut buf = [0u8; 32];
+    value
+        .into_u256()
+        .to_big_endian(&mut buf)
+        .expect("buffer holds exactly one field element");
+    U256::from_big_endian(&buf)
+}
 
-    Fq::from_slice(&buf).unwrap()
+fn check_coordinates(coordinates: &[U256]) -> Result<(), VerifyError> {
+    if coordinates.iter().any(|c| *c >= BASE_FIELD_MODULUS_U256) {
+        return Err(VerifyError::CoordinateNotInField);
+    }
+    Ok(())
 }
 
-#[cfg(feature = "casper")]
-pub fn alt_bn128_add() {}
+fn g1_from_bytes(bytes: &[u8; G1_BYTES]) -> Result<G1Point, VerifyError> {
+    let p = G1Point::new(
+        U256::from_big_endian(&bytes[..32]),
+        U256::from_big_endian(&bytes[32..]),
+    );
+    check_coordinates(&[p.x, p.y])?;
+    Ok(p)
+}
+
+fn g1_to_bytes(p: &G1Point) -> [u8; G1_BYTES] {
+    let mut bytes = [0u8; G1_BYTES];
+    p.x.to_big_endian(&mut bytes[..32]);
+    p.y.to_big_endian(&mut bytes[32..]);
+    bytes
+}
+
+fn g2_from_bytes(bytes: &[u8; G2_BYTES]) -> Result<G2Point, VerifyError> {
+    let coordinate = |i: usize| U256::from_big_endian(&bytes[32 * i..32 * (i + 1)]);
+    let p = G2Point::new(
+        [coordinate(0), coordinate(1)],
+        [coordinate(2), coordinate(3)],
+    );
+    check_coordinates(&[p.x[0], p.x[1], p.y[0], p.y[1]])?;
+    Ok(p)
+}
+
+fn g2_to_bytes(p: &G2Point) -> [u8; G2_BYTES] {
+    let mut bytes = [0u8; G2_BYTES];
+    for (chunk, value) in bytes.chunks_mut(32).zip(p.x.iter().chain(p.y.iter())) {
+        value.to_big_endian(chunk);
+    }
+    bytes
+}
+
+fn fr_from_bytes(bytes: &[u8; SCALAR_BYTES]) -> Result<Fr, VerifyError> {
+    fr_from_u256(U256::from_big_endian(bytes))
+}
+
+fn fr_to_bytes(k: &Fr) -> [u8; SCALAR_BYTES] {
+    let mut bytes = [0u8; SCALAR_BYTES];
+    fr_to_u256(*k).to_big_endian(&mut bytes);
+    bytes
+}
+
+// Zeropool checks curve and subgroup membership of every point it decodes, validation decodes
+// once up front so that errors are reported in the same order as on the other backends.
+impl Bn254Backend for Zeropool {
+    type G1 = G1Point;
+    type G2 = G2Point;
+    type Scalar = Fr;
+
+    fn g1_add(p: &G1Point, q: &G1Point) -> Result<G1Point, VerifyError> {
+        let p = point_from_coords(p.x, p.y)?;
+        let q = point_from_coords(q.x, q.y)?;
+        Ok(point_from_jacobian(p + q))
+    }
+
+    fn g1_mul(p: &G1Point, k: &Fr) -> Result<G1Point, VerifyError> {
+        let p = point_from_coords(p.x, p.y)?;
+        Ok(point_from_jacobian(p * *k))
+    }
+
+    fn g1_neg(p: &G1Point) -> Result<G1Point, VerifyError> {
+        Ok(point_from_jacobian(-point_from_coords(p.x, p.y)?))
+    }
+
+    fn pairing_check(pairs: &[(G1Point, G2Point)]) -> Result<bool, VerifyError> {
+        let pairs = pairs
+            .iter()
+            .map(|(a, b)| {
+                Ok((
+                    point_from_coords(a.x, a.y)?,
+                    g2_point_from_coords(b.x, b.y)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, VerifyError>>()?;
+        Ok(bn::pairing_batch(&pairs) == bn::Gt::one())
+    }
 
+    fn validate_g1(p: &G1Point) -> Result<(), VerifyError> {
+        point_from_coords(p.x, p.y).map(drop)
+    }
+
+    fn validate_g2(p: &G2Point) -> Result<(), VerifyError> {
+        g2_point_from_coords(p.x, p.y).map(drop)
+    }
+
+    fn g1_from_bytes(bytes: &[u8; G1_BYTES]) -> Result<G1Point, VerifyError> {
+        g1_from_bytes(bytes)
+    }
+
+    fn g1_to_bytes(p: &G1Point) -> [u8; G1_BYTES] {
+        g1_to_bytes(p)
+    }
+
+    fn g2_from_bytes(bytes: &[u8; G2_BYTES]) -> Result<G2Point, VerifyError> {
+        g2_from_bytes(bytes)
+    }
+
+    fn g2_to_bytes(p: &G2Point) -> [u8; G2_BYTES] {
+        g2_to_bytes(p)
+    }
+
+    fn scalar_zero() -> Fr {
+        Fr::zero()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8; SCALAR_BYTES]) -> Result<Fr, VerifyError> {
+        fr_from_bytes(bytes)
+    }
+
+    fn scalar_to_bytes(k: &Fr) -> [u8; SCALAR_BYTES] {
+        fr_to_bytes(k)
+    }
+}
+
+// The host rejects malformed input with an opaque error, so range checks are done up front.
+// Validation checks curve and subgroup membership in-crate with zeropool instead of relying on the
+// host. Scalar arithmetic for batching still uses zeropool.
 #[cfg(feature = "casper")]
-pub fn alt_bn128_mul() {}
+impl Bn254Backend for CasperHost {
+    type G1 = G1Point;
+    type G2 = G2Point;
+    type Scalar = Fr;
 
-#[cfg(not(feature = "casper"))]
-pub fn alt_bn128_add(x1: U256, y1: U256, x2: U256, y2: U256) -> (U256, U256) {
-    let p1 = point_from_coords(x1, y1);
-    let p2 = point_from_coords(x2, y2);
+    fn g1_add(p: &G1Point, q: &G1Point) -> Result<G1Point, VerifyError> {
+        check_coordinates(&[p.x, p.y, q.x, q.y])?;
+        let (x, y) = altbn128::alt_bn128_add(&p.x, &p.y, &q.x, &q.y)
+            .map_err(|_| VerifyError::PointNotOnCurve)?;
+        Ok(G1Point::new(x, y))
+    }
 
-    let mut x = U256::zero();
-    let mut y = U256::zero();
+    fn g1_mul(p: &G1Point, k: &Fr) -> Result<G1Point, VerifyError> {
+        check_coordinates(&[p.x, p.y])?;
+        let (x, y) = altbn128::alt_bn128_mul(&p.x, &p.y, &fr_to_u256(*k))
+            .map_err(|_| VerifyError::PointNotOnCurve)?;
+        Ok(G1Point::new(x, y))
+    }
+
+    fn g1_neg(p: &G1Point) -> Result<G1Point, VerifyError> {
+        let (x, y) = negate_g1_affine(p.x, p.y)?;
+        Ok(G1Point::new(x, y))
+    }
+
+    fn pairing_check(pairs: &[(G1Point, G2Point)]) -> Result<bool, VerifyError> {
+        let mut host_pairs = Vec::with_capacity(pairs.len());
+        for (a, b) in pairs {
+            check_coordinates(&[a.x, a.y, b.x[0], b.x[1], b.y[0], b.y[1]])?;
+            host_pairs.push(altbn128::Pair {
+                ax: a.x,
+                ay: a.y,
+                bax: b.x[0],
+                bay: b.x[1],
+                bbx: b.y[0],
+                bby: b.y[1],
+            });
+        }
+        altbn128::alt_bn128_pairing(&host_pairs).map_err(|_| VerifyError::PairingFailed)
+    }
+
+    fn validate_g1(p: &G1Point) -> Result<(), VerifyError> {
+        Zeropool::validate_g1(p)
+    }
+
+    fn validate_g2(p: &G2Point) -> Result<(), VerifyError> {
+        Zeropool::validate_g2(p)
+    }
+
+    fn g1_from_bytes(bytes: &[u8; G1_BYTES]) -> Result<G1Point, VerifyError> {
+        g1_from_bytes(bytes)
+    }
+
+    fn g1_to_bytes(p: &G1Point) -> [u8; G1_BYTES] {
+        g1_to_bytes(p)
+    }
+
+    fn g2_from_bytes(bytes: &[u8; G2_BYTES]) -> Result<G2Point, VerifyError> {
+        g2_from_bytes(bytes)
+    }
+
+    fn g2_to_bytes(p: &G2Point) -> [u8; G2_BYTES] {
+        g2_to_bytes(p)
+    }
+
+    fn scalar_zero() -> Fr {
+        Fr::zero()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8; SCALAR_BYTES]) -> Result<Fr, VerifyError> {
+        fr_from_bytes(bytes)
+    }
 
-    if let Some(sum) = AffineG1::from_jacobian(p1 + p2) {
-        x = fq_to_u256(sum.x());
-        y = fq_to_u256(sum.y());
+    fn scalar_to_bytes(k: &Fr) -> [u8; SCALAR_BYTES] {
+        fr_to_bytes(k)
     }
-    (x, y)
 }
 
-#[cfg(not(feature = "casper"))]
-pub fn alt_bn128_mul(x: U256, y: U256, scalar: U256) -> (U256, U256) {
-    let p = point_from_coords(x, y);
-
-    let mut x = U256::zero();
-    let mut y = U256::zero();
-    let fr = Fr::from_slice(&scalar.to_be_bytes()).unwrap();
-
-    if let Some(product) = AffineG1::from_jacobian(p * fr) {
-        x = fq_to_u256(product.x());
-        y = fq_to_u256(product.y());
-    }
-    (x, y)
-}
-
-pub fn alt_bn128_pairing(values: Vec<(U256, U256, U256, U256, U256, U256)>) -> bool {
-    let mut pairs = Vec::with_capacity(values.len());
-    for (ax, ay, bax, bay, bbx, bby) in values {
-        let ax = fq_from_u256(ax);
-        let ay = fq_from_u256(ay);
-        let bax: Fq = fq_from_u256(bax);
-        let bbx: Fq = fq_from_u256(bbx);
-        let bay = fq_from_u256(bay);
-        let bby = fq_from_u256(bby);
-
-        let g1_a = {
-            if ax.is_zero() && ay.is_zero() {
-                bn::G1::zero()
-            } else {
-                bn::AffineG1::new(ax, ay).unwrap().into()
-            }
-        };
-        let g1_b = {
-            let ba = bn::Fq2::new(bax, bay);
-            let bb = bn::Fq2::new(bbx, bby);
-
-            if ba.is_zero() && bb.is_zero() {
-                bn::G2::zero()
-            } else {
-                bn::AffineG2::new(ba, bb).unwrap().into()
+/// `ECADD` on the host function with the `casper` feature and on zeropool otherwise.
+pub fn alt_bn128_add(x1: U256, y1: U256, x2: U256, y2: U256) -> Result<(U256, U256), VerifyError> {
+    let sum = Backend::g1_add(&G1Point::new(x1, y1), &G1Point::new(x2, y2))?;
+    Ok((sum.x, sum.y))
+}
+
+/// `ECMUL`, rejecting scalars outside the scalar field.
+pub fn alt_bn128_mul(x: U256, y: U256, scalar: U256) -> Result<(U256, U256), VerifyError> {
+    let product = Backend::g1_mul(&G1Point::new(x, y), &fr_from_u256(scalar)?)?;
+    Ok((product.x, product.y))
+}
+
+/// `ECPAIRING` on `(ax, ay, bx.c0, bx.c1, by.c0, by.c1)` tuples, the order of the `altbn128` host
+/// function. EIP-197 input puts `c1` first, see `alt_bn128_pairing_check`.
+pub fn alt_bn128_pairing(
+    values: Vec<(U256, U256, U256, U256, U256, U256)>,
+) -> Result<bool, VerifyError> {
+    let pairs: Vec<(G1Point, G2Point)> = values
+        .into_iter()
+        .map(|(ax, ay, bx_c0, bx_c1, by_c0, by_c1)| {
+            (
+                G1Point::new(ax, ay),
+                G2Point::from_snarkjs([bx_c0, bx_c1], [by_c0, by_c1]),
+            )
+        })
+        .collect();
+    alt_bn128_pairing_check(&pairs)
+}
+
+/// `ECPAIRING` on points, returns whether `prod e(a_i, b_i) == 1`.
+pub fn alt_bn128_pairing_check(pairs: &[(G1Point, G2Point)]) -> Result<bool, VerifyError> {
+    Backend::pairing_check(pairs)
+}
+
+/// Applies `policy` to every public input, returning canonical scalars.
+pub fn canonical_inputs(
+    inputs: &PublicInputs,
+    policy: InputPolicy,
+) -> Result<PublicInputs, VerifyError> {
+    inputs
+        .iter()
+        .map(|input| match policy {
+            InputPolicy::RejectNonCanonical if *input >= SCALAR_FIELD_MODULUS_U256 => {
+                Err(VerifyError::ScalarOutOfRange)
             }
-        };
-        pairs.push((g1_a, g1_b));
+            InputPolicy::RejectNonCanonical => Ok(*input),
+            InputPolicy::Reduce => Ok(*input % SCALAR_FIELD_MODULUS_U256),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(types::PublicInputs)
+}
+
+fn input_scalars(inputs: &PublicInputs, policy: InputPolicy) -> Result<Vec<Fr>, VerifyError> {
+    canonical_inputs(inputs, policy)?
+        .iter()
+        .map(|input| fr_from_u256(*input))
+        .collect()
+}
+
+/// Checks that every verifying key point is on the curve and in the prime-order subgroup and that
+/// `ic` is not empty, e.g. before storing a key. Runs without the host functions.
+pub fn validate_vk(vk: &VerifyingKey) -> Result<(), VerifyError> {
+    groth16::validate_vk::<Backend>(vk)?;
+    if vk.ic.is_empty() {
+        return Err(VerifyError::EmptyVerifyingKey);
     }
+    Ok(())
+}
+
+/// Checks `e(-A, B) * e(alpha1, beta2) * e(vk_x, gamma2) * e(C, delta2) == 1`.
+pub fn verify(
+    vk: &VerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_with_options(vk, proof, inputs, &VerifyOptions::default())
+}
+
+/// Zeropool validates every point it decodes, so `options.validate_vk` only has an effect with the
+/// `casper` feature, where skipping it leaves the key points to the host.
+pub fn verify_with_options(
+    vk: &VerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    let inputs = input_scalars(inputs, options.inputs)?;
+    groth16::verify::<Backend>(vk, proof, &inputs, options)
+}
+
+/// Verifies a proof with gnark's Pedersen commitments, see `groth16::verify_committed`.
+pub fn verify_committed(
+    vk: &CommittedVerifyingKey,
+    proof: &CommittedProof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_committed_with_options(vk, proof, inputs, &VerifyOptions::default())
+}
+
+pub fn verify_committed_with_options(
+    vk: &CommittedVerifyingKey,
+    proof: &CommittedProof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    let inputs = input_scalars(inputs, options.inputs)?;
+    groth16::verify_committed::<Backend>(vk, proof, &inputs, options)
+}
+
+/// Verifies every `(proof, inputs)` pair of `batch` against `vk` with a single pairing check of
+/// `n + 3` pairs, see `groth16::verify_batch`.
+pub fn verify_batch(
+    vk: &VerifyingKey,
+    batch: &[(Proof, PublicInputs)],
+) -> Result<Option<usize>, VerifyError> {
+    verify_batch_with_options(vk, batch, &VerifyOptions::default())
+}
+
+pub fn verify_batch_with_options(
+    vk: &VerifyingKey,
+    batch: &[(Proof, PublicInputs)],
+    options: &VerifyOptions,
+) -> Result<Option<usize>, VerifyError> {
+    let batch = batch
+        .iter()
+        .map(|(proof, inputs)| Ok((*proof, input_scalars(inputs, options.inputs)?)))
+        .collect::<Result<Vec<_>, VerifyError>>()?;
+    groth16::verify_batch::<Backend>(vk, &batch, options)
+}
+
+/// A verifying key with every point decoded once, `e(alpha1, beta2)` evaluated and `gamma2`,
+/// `delta2` negated, so that a proof costs three Miller loops and one final exponentiation.
+///
+/// Zeropool does not expose prepared G2 points, so the line coefficients of `-gamma2` and
+/// `-delta2` are still derived per call. The Casper host only answers pairing checks, which rules
+/// out comparing against a cached `e(alpha1, beta2)`, so this is only available off-chain.
+#[cfg(not(feature = "casper"))]
+#[derive(Clone)]
+pub struct PreparedVerifyingKey {
+    vk: VerifyingKey,
+    alpha_beta: Gt,
+    gamma2_neg: G2,
+    delta2_neg: G2,
+}
+
+#[cfg(not(feature = "casper"))]
+impl PreparedVerifyingKey {
+    pub fn new(vk: VerifyingKey) -> Result<Self, VerifyError> {
+        let alpha1 = point_from_coords(vk.alpha1.x, vk.alpha1.y)?;
+        let beta2 = g2_point_from_coords(vk.beta2.x, vk.beta2.y)?;
+        let gamma2 = g2_point_from_coords(vk.gamma2.x, vk.gamma2.y)?;
+        let delta2 = g2_point_from_coords(vk.delta2.x, vk.delta2.y)?;
+        if vk.ic.is_empty() {
+            return Err(VerifyError::EmptyVerifyingKey);
+        }
+        for ic in &vk.ic {
+            point_from_coords(ic.x, ic.y)?;
+        }
+        Ok(PreparedVerifyingKey {
+            alpha_beta: bn::pairing(alpha1, beta2),
+            gamma2_neg: -gamma2,
+            delta2_neg: -delta2,
+            vk,
+        })
+    }
+
+    /// The key this was prepared from, validated by `new`.
+    pub fn vk(&self) -> &VerifyingKey {
+        &self.vk
+    }
+}
+
+#[cfg(not(feature = "casper"))]
+pub fn verify_prepared(
+    pvk: &PreparedVerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_prepared_with_options(pvk, proof, inputs, &VerifyOptions::default())
+}
+
+/// Checks `e(A, B) * e(vk_x, -gamma2) * e(C, -delta2) == e(alpha1, beta2)`.
+#[cfg(not(feature = "casper"))]
+pub fn verify_prepared_with_options(
+    pvk: &PreparedVerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    groth16::validate_proof::<Zeropool>(proof)?;
+    let inputs = input_scalars(inputs, options.inputs)?;
+    let a = point_from_coords(proof.a.x, proof.a.y)?;
+    let b = g2_point_from_coords(proof.b.x, proof.b.y)?;
+    let c = point_from_coords(proof.c.x, proof.c.y)?;
+    let vk_x = groth16::compute_vk_x::<Zeropool>(&pvk.vk.ic, &inputs)?;
+    let vk_x = point_from_coords(vk_x.x, vk_x.y)?;
 
-    bn::pairing_batch(pairs.as_slice()) == bn::Gt::one()
+    // pairs with an identity element contribute one and cannot be converted to affine form
+    let pairs: Vec<(G2, G1)> = [(b, a), (pvk.gamma2_neg, vk_x), (pvk.delta2_neg, c)]
+        .into_iter()
+        .filter(|(g2, g1)| !g2.is_zero() && !g1.is_zero())
+        .collect();
+    let miller_loop = bn::miller_loop_batch(&pairs).map_err(|_| VerifyError::PairingFailed)?;
+    let product = miller_loop
+        .final_exponentiation()
+        .ok_or(VerifyError::PairingFailed)?;
+    Ok(product == pvk.alpha_beta)
 }
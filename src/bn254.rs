@@ -1,17 +1,87 @@
-use std::str::FromStr;
+use alloc::vec::Vec;
 
-use ark_bn254::{self, Config, Fq, Fr, G1Affine, G2Affine};
-use ark_ec::{models::bn::Bn, pairing::Pairing, AffineRepr, CurveGroup};
+use ark_bn254::{self, Config, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::{
+    models::bn::{Bn, G2Prepared},
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
 use ark_ff::{BigInteger, PrimeField, Zero};
 use num_bigint::BigUint;
 
-use crate::BASE_FIELD_MODULUS;
+use crate::{
+    groth16::{self, Bn254Backend, G1_BYTES, G2_BYTES, SCALAR_BYTES},
+    types::{self, InputPolicy, VerifyOptions},
+    VerifyError,
+};
 
 pub type G1 = ark_bn254::g1::G1Affine;
 
-pub fn parse_biguint_to_fq(value: &str) -> Fq {
-    let big_int = BigUint::parse_bytes(value.as_bytes(), 10).unwrap();
-    Fq::from(big_int)
+pub type Proof = types::Proof<G1Affine, G2Affine>;
+pub type VerifyingKey = types::VerifyingKey<G1Affine, G2Affine>;
+pub type PublicInputs = types::PublicInputs<BigUint>;
+pub type CommittedVerifyingKey = types::CommittedVerifyingKey<G1Affine, G2Affine>;
+pub type CommittedProof = types::CommittedProof<G1Affine, G2Affine>;
+
+/// Builds a G1 point, rejecting coordinates off the curve. G1 has cofactor 1, so every point on the
+/// curve is in the prime-order subgroup. `(0, 0)` is the point at infinity.
+pub fn g1_from_coords(x: Fq, y: Fq) -> Result<G1Affine, VerifyError> {
+    let p = g1_unchecked(x, y);
+    validate_g1(&p)?;
+    Ok(p)
+}
+
+/// Builds a G2 point, rejecting coordinates off the twist and points outside the prime-order
+/// subgroup, which is a strict subset of the twist's points. `(0, 0)` is the point at infinity.
+pub fn g2_from_coords(x: Fq2, y: Fq2) -> Result<G2Affine, VerifyError> {
+    let p = g2_unchecked(x, y);
+    validate_g2(&p)?;
+    Ok(p)
+}
+
+// arkworks flags the identity instead of giving it coordinates, `new_unchecked(0, 0)` is a point
+// off the curve. Every conversion into `G1Affine` and `G2Affine` goes through these two.
+pub(crate) fn g1_unchecked(x: Fq, y: Fq) -> G1Affine {
+    if x.is_zero() && y.is_zero() {
+        return G1Affine::identity();
+    }
+    G1Affine::new_unchecked(x, y)
+}
+
+pub(crate) fn g2_unchecked(x: Fq2, y: Fq2) -> G2Affine {
+    if x.is_zero() && y.is_zero() {
+        return G2Affine::identity();
+    }
+    G2Affine::new_unchecked(x, y)
+}
+
+pub fn validate_g1(p: &G1Affine) -> Result<(), VerifyError> {
+    if !p.is_on_curve() {
+        return Err(VerifyError::PointNotOnCurve);
+    }
+    Ok(())
+}
+
+pub fn validate_g2(p: &G2Affine) -> Result<(), VerifyError> {
+    if !p.is_on_curve() {
+        return Err(VerifyError::PointNotOnCurve);
+    }
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(VerifyError::PointNotInSubgroup);
+    }
+    Ok(())
+}
+
+pub fn parse_biguint_to_fq(value: &str) -> Result<Fq, VerifyError> {
+    let big_int = BigUint::parse_bytes(value.as_bytes(), 10).ok_or(VerifyError::InvalidEncoding)?;
+    fq_from_biguint(&big_int)
+}
+
+fn fq_from_biguint(value: &BigUint) -> Result<Fq, VerifyError> {
+    if *value >= BigUint::from(Fq::MODULUS) {
+        return Err(VerifyError::CoordinateNotInField);
+    }
+    Ok(Fq::from(value.clone()))
 }
 
 fn fq_to_biguint(x_fq: &Fq) -> BigUint {
@@ -19,45 +89,167 @@ fn fq_to_biguint(x_fq: &Fq) -> BigUint {
     BigUint::from_bytes_le(&bigint_repr.to_bytes_le())
 }
 
+/// Negates `p`, the identity and `(0, 0)` both negate to the identity.
 pub fn negate_g1_affine(p: G1Affine) -> G1Affine {
-    let x_fq = p.x().unwrap();
-    let y_coord: BigUint = fq_to_biguint(p.y().unwrap());
-    let base_field_modulus_biguint = BigUint::from_str(BASE_FIELD_MODULUS).unwrap();
-    if y_coord == BigUint::ZERO && fq_to_biguint(x_fq) == BigUint::ZERO {
-        G1Affine::new_unchecked(parse_biguint_to_fq("0"), parse_biguint_to_fq("0"))
-    } else {
-        let neg_y_coord =
-            (base_field_modulus_biguint.clone() - y_coord) % base_field_modulus_biguint;
-        G1Affine::new_unchecked(*x_fq, Fq::from(neg_y_coord))
-    }
-}
-
-pub fn add_g1_as_coordinates(p_x: BigUint, p_y: BigUint, q_x: BigUint, q_y: BigUint) -> G1 {
-    let p = G1::new_unchecked(
-        parse_biguint_to_fq(&p_x.to_string()),
-        parse_biguint_to_fq(&p_y.to_string()),
-    );
-    let q = G1::new_unchecked(
-        parse_biguint_to_fq(&q_x.to_string()),
-        parse_biguint_to_fq(&q_y.to_string()),
-    );
-    (p + q).into_affine()
+    match p.xy() {
+        Some((x, y)) => g1_unchecked(*x, -*y),
+        None => G1Affine::identity(),
+    }
+}
+
+pub fn add_g1_as_coordinates(
+    p_x: BigUint,
+    p_y: BigUint,
+    q_x: BigUint,
+    q_y: BigUint,
+) -> Result<G1, VerifyError> {
+    let p = g1_unchecked(fq_from_biguint(&p_x)?, fq_from_biguint(&p_y)?);
+    let q = g1_unchecked(fq_from_biguint(&q_x)?, fq_from_biguint(&q_y)?);
+    Ok((p + q).into_affine())
 }
 
 pub fn extract_g1_coordinates(p: G1) -> (BigUint, BigUint) {
-    (fq_to_biguint(p.x().unwrap()), fq_to_biguint(p.y().unwrap()))
+    match p.xy() {
+        Some((x, y)) => (fq_to_biguint(x), fq_to_biguint(y)),
+        None => (BigUint::ZERO, BigUint::ZERO),
+    }
 }
 
-fn scalar_mul(p_x: BigUint, p_y: BigUint, k: BigUint) -> G1 {
-    let p = G1::new_unchecked(
-        parse_biguint_to_fq(&p_x.to_string()),
-        parse_biguint_to_fq(&p_y.to_string()),
-    );
-    let scalar = Fr::from_be_bytes_mod_order(&k.to_bytes_be());
-    (p.into_group() * scalar).into_affine()
+/// Applies `policy` to every public input, returning canonical scalars.
+pub fn canonical_inputs(
+    inputs: &PublicInputs,
+    policy: InputPolicy,
+) -> Result<PublicInputs, VerifyError> {
+    let modulus = BigUint::from(Fr::MODULUS);
+    inputs
+        .iter()
+        .map(|input| match policy {
+            InputPolicy::RejectNonCanonical if *input >= modulus => {
+                Err(VerifyError::ScalarOutOfRange)
+            }
+            InputPolicy::RejectNonCanonical => Ok(input.clone()),
+            InputPolicy::Reduce => Ok(input % &modulus),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(types::PublicInputs)
+}
+
+/// Arkworks in software.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Arkworks;
+
+fn fq_from_bytes(bytes: &[u8]) -> Result<Fq, VerifyError> {
+    fq_from_biguint(&BigUint::from_bytes_be(bytes))
+}
+
+fn write_fq(value: &Fq, out: &mut [u8]) {
+    out.copy_from_slice(&value.into_bigint().to_bytes_be());
+}
+
+impl Bn254Backend for Arkworks {
+    type G1 = G1Affine;
+    type G2 = G2Affine;
+    type Scalar = Fr;
+
+    fn g1_add(p: &G1Affine, q: &G1Affine) -> Result<G1Affine, VerifyError> {
+        Ok((*p + *q).into_affine())
+    }
+
+    fn g1_mul(p: &G1Affine, k: &Fr) -> Result<G1Affine, VerifyError> {
+        Ok((*p * *k).into_affine())
+    }
+
+    fn g1_neg(p: &G1Affine) -> Result<G1Affine, VerifyError> {
+        Ok(-*p)
+    }
+
+    fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> Result<bool, VerifyError> {
+        let (a, b): (Vec<G1Affine>, Vec<G2Affine>) = pairs.iter().copied().unzip();
+        Ok(Bn::<Config>::multi_pairing(a, b).is_zero())
+    }
+
+    fn validate_g1(p: &G1Affine) -> Result<(), VerifyError> {
+        validate_g1(p)
+    }
+
+    fn validate_g2(p: &G2Affine) -> Result<(), VerifyError> {
+        validate_g2(p)
+    }
+
+    fn g1_from_bytes(bytes: &[u8; G1_BYTES]) -> Result<G1Affine, VerifyError> {
+        Ok(g1_unchecked(
+            fq_from_bytes(&bytes[..32])?,
+            fq_from_bytes(&bytes[32..])?,
+        ))
+    }
+
+    fn g1_to_bytes(p: &G1Affine) -> [u8; G1_BYTES] {
+        let mut bytes = [0u8; G1_BYTES];
+        if let Some((x, y)) = p.xy() {
+            write_fq(x, &mut bytes[..32]);
+            write_fq(y, &mut bytes[32..]);
+        }
+        bytes
+    }
+
+    fn g2_from_bytes(bytes: &[u8; G2_BYTES]) -> Result<G2Affine, VerifyError> {
+        let coordinate = |i: usize| fq_from_bytes(&bytes[32 * i..32 * (i + 1)]);
+        Ok(g2_unchecked(
+            Fq2::new(coordinate(0)?, coordinate(1)?),
+            Fq2::new(coordinate(2)?, coordinate(3)?),
+        ))
+    }
+
+    fn g2_to_bytes(p: &G2Affine) -> [u8; G2_BYTES] {
+        let mut bytes = [0u8; G2_BYTES];
+        if let Some((x, y)) = p.xy() {
+            for (chunk, value) in bytes.chunks_mut(32).zip([x.c0, x.c1, y.c0, y.c1]) {
+                write_fq(&value, chunk);
+            }
+        }
+        bytes
+    }
+
+    fn scalar_zero() -> Fr {
+        Fr::zero()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8; SCALAR_BYTES]) -> Result<Fr, VerifyError> {
+        let value = BigUint::from_bytes_be(bytes);
+        if value >= BigUint::from(Fr::MODULUS) {
+            return Err(VerifyError::ScalarOutOfRange);
+        }
+        Ok(Fr::from(value))
+    }
+
+    fn scalar_to_bytes(k: &Fr) -> [u8; SCALAR_BYTES] {
+        let mut bytes = [0u8; SCALAR_BYTES];
+        bytes.copy_from_slice(&k.into_bigint().to_bytes_be());
+        bytes
+    }
 }
 
-// todo: generic public inputs
+fn input_scalars(inputs: &PublicInputs, policy: InputPolicy) -> Result<Vec<Fr>, VerifyError> {
+    Ok(canonical_inputs(inputs, policy)?
+        .iter()
+        .map(|input| Fr::from(input.clone()))
+        .collect())
+}
+
+/// Checks `e(-A, B) * e(alpha1, beta2) * e(vk_x, gamma2) * e(C, delta2) == 1`, validating every
+/// proof and verifying key point first.
+pub fn verify(
+    vk: &VerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_with_options(vk, proof, inputs, &VerifyOptions::default())
+}
+
+/// The verifier before `verify`, kept for existing callers. A malformed key, proof or input is
+/// `false` rather than a `VerifyError`.
+#[deprecated(note = "use `bn254::verify` with a `VerifyingKey`, `Proof` and `PublicInputs`")]
+#[allow(clippy::too_many_arguments)]
 pub fn verify_groth16_proof(
     pi_a: G1Affine,
     pi_b: G2Affine,
@@ -69,26 +261,126 @@ pub fn verify_groth16_proof(
     ics: Vec<G1Affine>,
     inputs: Vec<BigUint>,
 ) -> bool {
-    let mut vk_x: G1 = ics[0];
-    for (idx, ic) in ics.into_iter().enumerate().skip(1) {
-        let ic_coords = extract_g1_coordinates(ic);
-        let ic_scalar: G1 = scalar_mul(ic_coords.0, ic_coords.1, inputs[idx - 1].clone());
-        println!("processed input: {}, with ic: {}", inputs[idx - 1], idx);
-        let ic_scalar_coords = extract_g1_coordinates(ic_scalar);
-        let vk_x_as_coordinates = extract_g1_coordinates(vk_x);
-        let vk_x_as_coords = vk_x_as_coordinates.clone();
-        vk_x = add_g1_as_coordinates(
-            vk_x_as_coords.0,
-            vk_x_as_coords.1,
-            ic_scalar_coords.0,
-            ic_scalar_coords.1,
-        );
-    }
-
-    // compute pairing result and return is_zero?
-    <Bn<Config> as Pairing>::multi_pairing(
-        vec![negate_g1_affine(pi_a), vk_alpha1, vk_x, pi_c],
-        vec![pi_b, vk_beta2, vk_gamma2, vk_delta2],
-    )
-    .is_zero()
+    let vk = VerifyingKey {
+        alpha1: vk_alpha1,
+        beta2: vk_beta2,
+        gamma2: vk_gamma2,
+        delta2: vk_delta2,
+        ic: ics,
+    };
+    let proof = Proof {
+        a: pi_a,
+        b: pi_b,
+        c: pi_c,
+    };
+    verify(&vk, &proof, &types::PublicInputs(inputs)) == Ok(true)
+}
+
+pub fn verify_with_options(
+    vk: &VerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    let inputs = input_scalars(inputs, options.inputs)?;
+    groth16::verify::<Arkworks>(vk, proof, &inputs, options)
+}
+
+/// Verifies a proof with gnark's Pedersen commitments, see `groth16::verify_committed`.
+pub fn verify_committed(
+    vk: &CommittedVerifyingKey,
+    proof: &CommittedProof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_committed_with_options(vk, proof, inputs, &VerifyOptions::default())
+}
+
+pub fn verify_committed_with_options(
+    vk: &CommittedVerifyingKey,
+    proof: &CommittedProof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    let inputs = input_scalars(inputs, options.inputs)?;
+    groth16::verify_committed::<Arkworks>(vk, proof, &inputs, options)
+}
+
+/// Verifies every `(proof, inputs)` pair of `batch` against `vk` with `n + 3` Miller loops and a
+/// single final exponentiation, see `groth16::verify_batch`.
+pub fn verify_batch(
+    vk: &VerifyingKey,
+    batch: &[(Proof, PublicInputs)],
+) -> Result<Option<usize>, VerifyError> {
+    verify_batch_with_options(vk, batch, &VerifyOptions::default())
+}
+
+pub fn verify_batch_with_options(
+    vk: &VerifyingKey,
+    batch: &[(Proof, PublicInputs)],
+    options: &VerifyOptions,
+) -> Result<Option<usize>, VerifyError> {
+    let batch = batch
+        .iter()
+        .map(|(proof, inputs)| Ok((*proof, input_scalars(inputs, options.inputs)?)))
+        .collect::<Result<Vec<_>, VerifyError>>()?;
+    groth16::verify_batch::<Arkworks>(vk, &batch, options)
+}
+
+/// A verifying key with `e(alpha1, beta2)` evaluated and `-gamma2`, `-delta2` prepared for the
+/// Miller loop, so that a proof costs three Miller loops and one final exponentiation.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey {
+    vk: VerifyingKey,
+    alpha_beta: PairingOutput<Bn<Config>>,
+    gamma2_neg: G2Prepared<Config>,
+    delta2_neg: G2Prepared<Config>,
+}
+
+impl PreparedVerifyingKey {
+    /// Validates every point of `vk` once, proofs checked against the prepared key skip this.
+    pub fn new(vk: VerifyingKey) -> Result<Self, VerifyError> {
+        groth16::validate_vk::<Arkworks>(&vk)?;
+        Ok(PreparedVerifyingKey {
+            alpha_beta: Bn::<Config>::pairing(vk.alpha1, vk.beta2),
+            gamma2_neg: (-vk.gamma2).into(),
+            delta2_neg: (-vk.delta2).into(),
+            vk,
+        })
+    }
+
+    /// The key this was prepared from, validated by `new`.
+    pub fn vk(&self) -> &VerifyingKey {
+        &self.vk
+    }
+}
+
+pub fn verify_prepared(
+    pvk: &PreparedVerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+) -> Result<bool, VerifyError> {
+    verify_prepared_with_options(pvk, proof, inputs, &VerifyOptions::default())
+}
+
+/// Checks `e(A, B) * e(vk_x, -gamma2) * e(C, -delta2) == e(alpha1, beta2)`. `validate_vk` is
+/// ignored, the key was validated by `PreparedVerifyingKey::new`.
+pub fn verify_prepared_with_options(
+    pvk: &PreparedVerifyingKey,
+    proof: &Proof,
+    inputs: &PublicInputs,
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    groth16::validate_proof::<Arkworks>(proof)?;
+    let inputs = input_scalars(inputs, options.inputs)?;
+    let vk_x = groth16::compute_vk_x::<Arkworks>(&pvk.vk.ic, &inputs)?;
+
+    let miller_loop = Bn::<Config>::multi_miller_loop(
+        [proof.a, vk_x, proof.c],
+        [
+            G2Prepared::from(proof.b),
+            pvk.gamma2_neg.clone(),
+            pvk.delta2_neg.clone(),
+        ],
+    );
+    Ok(Bn::<Config>::final_exponentiation(miller_loop) == Some(pvk.alpha_beta))
 }
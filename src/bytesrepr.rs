@@ -0,0 +1,230 @@
+// Casper serialization of the `bn` proof types, so they can be passed as runtime args and stored in
+// dictionaries.
+//
+// Layout, all coordinates 32 byte big-endian:
+//   G1Point       x || y                                          64 bytes, ByteArray(64)
+//   G2Point       x.c0 || x.c1 || y.c0 || y.c1                    128 bytes, ByteArray(128)
+//   Proof         a || b || c                                     256 bytes, ByteArray(256)
+//   VerifyingKey  u32 LE length || alpha1 || beta2 || gamma2 || delta2 || ic[0] || ic[1] || ...
+//                 the same encoding as `Bytes`, List(U8)
+//   PublicInputs  `Vec<U256>`, List(U256)
+use alloc::{boxed::Box, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLTyped, U256,
+};
+
+use crate::bn::{G1Point, G2Point, Proof, PublicInputs, VerifyingKey};
+use crate::types;
+
+const FQ_SERIALIZED_LENGTH: usize = 32;
+pub const G1_SERIALIZED_LENGTH: usize = 2 * FQ_SERIALIZED_LENGTH;
+pub const G2_SERIALIZED_LENGTH: usize = 4 * FQ_SERIALIZED_LENGTH;
+pub const PROOF_SERIALIZED_LENGTH: usize = 2 * G1_SERIALIZED_LENGTH + G2_SERIALIZED_LENGTH;
+const VK_FIXED_LENGTH: usize = G1_SERIALIZED_LENGTH + 3 * G2_SERIALIZED_LENGTH;
+
+fn write_fq(value: &U256, writer: &mut Vec<u8>) {
+    let mut buf = [0u8; FQ_SERIALIZED_LENGTH];
+    value.to_big_endian(&mut buf);
+    writer.extend_from_slice(&buf);
+}
+
+fn read_fq(bytes: &[u8]) -> Result<(U256, &[u8]), Error> {
+    let (value, remainder) = bytesrepr::safe_split_at(bytes, FQ_SERIALIZED_LENGTH)?;
+    Ok((U256::from_big_endian(value), remainder))
+}
+
+impl ToBytes for G1Point {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        G1_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        write_fq(&self.x, writer);
+        write_fq(&self.y, writer);
+        Ok(())
+    }
+}
+
+impl FromBytes for G1Point {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (x, remainder) = read_fq(bytes)?;
+        let (y, remainder) = read_fq(remainder)?;
+        Ok((G1Point::new(x, y), remainder))
+    }
+}
+
+impl CLTyped for G1Point {
+    fn cl_type() -> CLType {
+        CLType::ByteArray(G1_SERIALIZED_LENGTH as u32)
+    }
+}
+
+impl ToBytes for G2Point {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        G2_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        for value in self.x.iter().chain(self.y.iter()) {
+            write_fq(value, writer);
+        }
+        Ok(())
+    }
+}
+
+impl FromBytes for G2Point {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (x0, remainder) = read_fq(bytes)?;
+        let (x1, remainder) = read_fq(remainder)?;
+        let (y0, remainder) = read_fq(remainder)?;
+        let (y1, remainder) = read_fq(remainder)?;
+        Ok((G2Point::new([x0, x1], [y0, y1]), remainder))
+    }
+}
+
+impl CLTyped for G2Point {
+    fn cl_type() -> CLType {
+        CLType::ByteArray(G2_SERIALIZED_LENGTH as u32)
+    }
+}
+
+impl ToBytes for Proof {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        PROOF_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.a.write_bytes(writer)?;
+        self.b.write_bytes(writer)?;
+        self.c.write_bytes(writer)
+    }
+}
+
+impl FromBytes for Proof {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (a, remainder) = G1Point::from_bytes(bytes)?;
+        let (b, remainder) = G2Point::from_bytes(remainder)?;
+        let (c, remainder) = G1Point::from_bytes(remainder)?;
+        Ok((types::Proof { a, b, c }, remainder))
+    }
+}
+
+impl CLTyped for Proof {
+    fn cl_type() -> CLType {
+        CLType::ByteArray(PROOF_SERIALIZED_LENGTH as u32)
+    }
+}
+
+impl ToBytes for VerifyingKey {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + VK_FIXED_LENGTH + self.ic.len() * G1_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        let length = VK_FIXED_LENGTH + self.ic.len() * G1_SERIALIZED_LENGTH;
+        u32::try_from(length)
+            .map_err(|_| Error::NotRepresentable)?
+            .write_bytes(writer)?;
+        self.alpha1.write_bytes(writer)?;
+        self.beta2.write_bytes(writer)?;
+        self.gamma2.write_bytes(writer)?;
+        self.delta2.write_bytes(writer)?;
+        for ic in &self.ic {
+            ic.write_bytes(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromBytes for VerifyingKey {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (length, remainder) = u32::from_bytes(bytes)?;
+        let length = length as usize;
+        if length < VK_FIXED_LENGTH + G1_SERIALIZED_LENGTH
+            || !(length - VK_FIXED_LENGTH).is_multiple_of(G1_SERIALIZED_LENGTH)
+        {
+            return Err(Error::Formatting);
+        }
+        let (body, remainder) = bytesrepr::safe_split_at(remainder, length)?;
+
+        let (alpha1, body) = G1Point::from_bytes(body)?;
+        let (beta2, body) = G2Point::from_bytes(body)?;
+        let (gamma2, body) = G2Point::from_bytes(body)?;
+        let (delta2, mut body) = G2Point::from_bytes(body)?;
+        let mut ic = Vec::with_capacity(body.len() / G1_SERIALIZED_LENGTH);
+        while !body.is_empty() {
+            let (point, rest) = G1Point::from_bytes(body)?;
+            ic.push(point);
+            body = rest;
+        }
+        Ok((
+            types::VerifyingKey {
+                alpha1,
+                beta2,
+                gamma2,
+                delta2,
+                ic,
+            },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for VerifyingKey {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(CLType::U8))
+    }
+}
+
+impl ToBytes for PublicInputs {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
+}
+
+impl FromBytes for PublicInputs {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (inputs, remainder) = Vec::<U256>::from_bytes(bytes)?;
+        Ok((types::PublicInputs(inputs), remainder))
+    }
+}
+
+impl CLTyped for PublicInputs {
+    fn cl_type() -> CLType {
+        Vec::<U256>::cl_type()
+    }
+}
@@ -0,0 +1,138 @@
+// gnark's Pedersen commitment extension to Groth16 (BSB22, https://eprint.iacr.org/2022/1072):
+// every commitment is hashed with the public inputs it commits to into one more public input, and a
+// single proof of knowledge covers the openings of all commitments.
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    groth16::{Bn254Backend, SCALAR_BYTES},
+    types::CommitmentKey,
+    VerifyError,
+};
+
+/// Domain separation tag of the public input derived from a commitment.
+pub const COMMITMENT_DST: &[u8] = b"bsb22-commitment";
+/// Domain separation tag of the challenge that batches the proofs of knowledge.
+pub const CHALLENGE_DST: &[u8] = b"G16-BSB22";
+
+// Bytes hashed per scalar, 128 bits more than the modulus so the reduction is close to uniform
+const HASH_TO_SCALAR_BYTES: usize = 48;
+
+/// `expand_message_xmd` from RFC 9380 with SHA-256. A `len` above `255 * 32` or a `dst` longer than
+/// 255 bytes, which the RFC rules out, is `VerifyError::InvalidEncoding`.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>, VerifyError> {
+    if len > 255 * 32 || dst.len() > 255 {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    let ell = len.div_ceil(32);
+    let dst_len = [dst.len() as u8];
+    let b0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    let mut bytes = Vec::with_capacity(ell * 32);
+    let mut b = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    bytes.extend_from_slice(&b);
+    for i in 2..=ell {
+        let mut xored = b0;
+        for (x, y) in xored.iter_mut().zip(b) {
+            *x ^= y;
+        }
+        b = Sha256::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        bytes.extend_from_slice(&b);
+    }
+    bytes.truncate(len);
+    Ok(bytes)
+}
+
+/// Hashes `msg` to a scalar as gnark's `fr.Hash`: 48 bytes of `expand_message_xmd`, read
+/// big-endian and reduced modulo `SCALAR_FIELD_MODULUS`.
+pub fn hash_to_scalar<B: Bn254Backend>(msg: &[u8], dst: &[u8]) -> Result<B::Scalar, VerifyError> {
+    let bytes = expand_message_xmd(msg, dst, HASH_TO_SCALAR_BYTES)?;
+    // 128 bit limbs are always below the modulus, so no input to `scalar_from_bytes` is rejected
+    let limb = |chunk: &[u8]| {
+        let mut scalar = [0u8; SCALAR_BYTES];
+        scalar[SCALAR_BYTES - chunk.len()..].copy_from_slice(chunk);
+        B::scalar_from_bytes(&scalar)
+    };
+    let mut shift = [0u8; SCALAR_BYTES];
+    shift[SCALAR_BYTES - 17] = 1;
+    let shift = B::scalar_from_bytes(&shift)?;
+    bytes.chunks(16).try_fold(
+        B::scalar_zero(),
+        |acc, chunk| Ok(acc * shift + limb(chunk)?),
+    )
+}
+
+/// Derives the public input of every commitment, hashing the uncompressed commitment with the
+/// public inputs it commits to. An index may refer to the input of an earlier commitment.
+pub(crate) fn commitment_inputs<B: Bn254Backend>(
+    public_committed: &[Vec<usize>],
+    commitments: &[B::G1],
+    inputs: &[B::Scalar],
+) -> Result<Vec<B::Scalar>, VerifyError> {
+    let mut extended = inputs.to_vec();
+    for (committed, commitment) in public_committed.iter().zip(commitments) {
+        let mut msg = B::g1_to_bytes(commitment).to_vec();
+        for &index in committed {
+            let input = index
+                .checked_sub(1)
+                .and_then(|i| extended.get(i))
+                .ok_or(VerifyError::InvalidEncoding)?;
+            msg.extend_from_slice(&B::scalar_to_bytes(input));
+        }
+        extended.push(hash_to_scalar::<B>(&msg, COMMITMENT_DST)?);
+    }
+    Ok(extended.split_off(inputs.len()))
+}
+
+/// Checks `prod e(r^i C_i, g_sigma_neg_i) * e(pok, g) == 1`, where `r` is hashed from the
+/// commitment inputs. With `pok = sum(r^i sigma_i C_i)` every factor cancels.
+pub(crate) fn check_pok<B: Bn254Backend>(
+    keys: &[CommitmentKey<B::G2>],
+    commitments: &[B::G1],
+    commitment_inputs: &[B::Scalar],
+    pok: &B::G1,
+) -> Result<bool, VerifyError> {
+    let Some(first) = keys.first() else {
+        return Ok(true);
+    };
+    // gnark folds the proofs of knowledge under one `g`, keys from separate setups cannot be batched
+    let g = B::g2_to_bytes(&first.g);
+    if keys.iter().any(|key| B::g2_to_bytes(&key.g) != g) {
+        return Err(VerifyError::Unsupported);
+    }
+    let transcript: Vec<u8> = commitment_inputs
+        .iter()
+        .flat_map(|input| B::scalar_to_bytes(input))
+        .collect();
+    let challenge = hash_to_scalar::<B>(&transcript, CHALLENGE_DST)?;
+
+    let mut pairs = Vec::with_capacity(keys.len() + 1);
+    let mut r: Option<B::Scalar> = None;
+    for (key, commitment) in keys.iter().zip(commitments) {
+        let folded = match r {
+            Some(r) => B::g1_mul(commitment, &r)?,
+            None => commitment.clone(),
+        };
+        pairs.push((folded, key.g_sigma_neg.clone()));
+        r = Some(r.map_or(challenge, |r| r * challenge));
+    }
+    pairs.push((pok.clone(), first.g.clone()));
+    B::pairing_check(&pairs)
+}
@@ -0,0 +1,80 @@
+use core::fmt;
+
+/// Reasons a proof, verifying key or public input is rejected before the pairing check decides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// A G1 or G2 point does not satisfy the curve equation.
+    PointNotOnCurve,
+    /// A G2 point lies on the curve but outside the prime-order subgroup.
+    PointNotInSubgroup,
+    /// A proof element `A`, `B` or `C` is the point at infinity.
+    PointAtInfinity,
+    /// A coordinate is not a canonical element of the base field.
+    CoordinateNotInField,
+    /// A scalar is not a canonical element of the scalar field.
+    ScalarOutOfRange,
+    /// The verifying key expects `expected` public inputs but `got` were supplied.
+    InputCountMismatch { expected: usize, got: usize },
+    /// The verifying key has `expected` commitment keys but the proof carries `got` commitments.
+    CommitmentCountMismatch { expected: usize, got: usize },
+    /// The verifying key carries no IC points.
+    EmptyVerifyingKey,
+    /// A number could not be parsed from its textual representation.
+    InvalidEncoding,
+    /// A JSON document does not have the expected shape.
+    InvalidJson,
+    /// The input targets a protocol, curve or feature this crate does not verify.
+    Unsupported,
+    /// The pairing could not be evaluated.
+    PairingFailed,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::PointNotOnCurve => write!(f, "point is not on the curve"),
+            VerifyError::PointNotInSubgroup => {
+                write!(f, "point is not in the prime-order subgroup")
+            }
+            VerifyError::PointAtInfinity => write!(f, "proof element is the point at infinity"),
+            VerifyError::CoordinateNotInField => write!(f, "coordinate is not in the base field"),
+            VerifyError::ScalarOutOfRange => write!(f, "scalar is not in the scalar field"),
+            VerifyError::InputCountMismatch { expected, got } => {
+                write!(f, "expected {} public inputs, got {}", expected, got)
+            }
+            VerifyError::CommitmentCountMismatch { expected, got } => {
+                write!(f, "expected {} commitments, got {}", expected, got)
+            }
+            VerifyError::EmptyVerifyingKey => write!(f, "verifying key has no IC points"),
+            VerifyError::InvalidEncoding => write!(f, "invalid number encoding"),
+            VerifyError::InvalidJson => write!(f, "malformed JSON document"),
+            VerifyError::Unsupported => write!(f, "unsupported protocol, curve or feature"),
+            VerifyError::PairingFailed => write!(f, "pairing could not be evaluated"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyError {}
+
+/// Stable revert codes, reported by Casper as `ApiError::User(code)`.
+#[cfg(feature = "casper")]
+impl From<VerifyError> for casper_types::ApiError {
+    fn from(err: VerifyError) -> Self {
+        let code = match err {
+            VerifyError::PointNotOnCurve => 1,
+            VerifyError::PointNotInSubgroup => 2,
+            VerifyError::CoordinateNotInField => 3,
+            VerifyError::ScalarOutOfRange => 4,
+            VerifyError::InputCountMismatch { .. } => 5,
+            VerifyError::EmptyVerifyingKey => 6,
+            VerifyError::InvalidEncoding => 7,
+            VerifyError::InvalidJson => 8,
+            VerifyError::Unsupported => 9,
+            VerifyError::PairingFailed => 10,
+            VerifyError::CommitmentCountMismatch { .. } => 11,
+            VerifyError::PointAtInfinity => 12,
+        };
+        casper_types::ApiError::User(code)
+    }
+}
@@ -0,0 +1,597 @@
+// Loaders for gnark's binary BN254 Groth16 encoding, as written by `VerifyingKey.WriteTo`,
+// `WriteRawTo` and `Proof.WriteTo` in gnark v0.10 and later, and for public witnesses from
+// `witness.MarshalBinary`. Integers are big-endian, slices are prefixed with a `u32` length and
+// points are compressed or uncompressed independently, so one file may mix both. Keys and proofs
+// with Pedersen commitments only convert into the `Committed*` types.
+use alloc::vec::Vec;
+
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+#[cfg(feature = "ark")]
+use num_bigint::BigUint;
+
+#[cfg(feature = "zeropool")]
+use crate::bn::{self, Zeropool};
+#[cfg(feature = "ark")]
+use crate::bn254::{self, Arkworks};
+use crate::{
+    groth16::{Bn254Backend, G1_BYTES, G2_BYTES},
+    types, VerifyError,
+};
+
+/// A big-endian base field element, as it appears in the encoding.
+pub type Coordinate = [u8; 32];
+
+// Metadata in the two most significant bits of a point's first byte, which are never set in a
+// coordinate since the base field modulus has 254 bits. `0b10 << 6` is a compressed point with the
+// smaller `y`.
+const FLAG_MASK: u8 = 0b11 << 6;
+const FLAG_UNCOMPRESSED: u8 = 0b00 << 6;
+const FLAG_COMPRESSED_INFINITY: u8 = 0b01 << 6;
+const FLAG_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+
+/// A G1 point as stored by gnark. Uncompressed points with both coordinates zero are the point at
+/// infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GnarkG1 {
+    Uncompressed {
+        x: Coordinate,
+        y: Coordinate,
+    },
+    /// `x` and whether `y` is the larger of its two candidates, i.e. greater than `(p - 1) / 2`.
+    Compressed {
+        x: Coordinate,
+        largest: bool,
+    },
+    CompressedInfinity,
+}
+
+/// A G2 point as stored by gnark, with coefficients ordered `[c0, c1]`. The encoding itself puts
+/// `c1` first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GnarkG2 {
+    Uncompressed {
+        x: [Coordinate; 2],
+        y: [Coordinate; 2],
+    },
+    /// `x` and whether `y` is the larger candidate, compared on `y.c1` unless it is zero.
+    Compressed {
+        x: [Coordinate; 2],
+        largest: bool,
+    },
+    CompressedInfinity,
+}
+
+/// A Pedersen commitment key, `G` and `-sigma G` in G2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GnarkCommitmentKey {
+    pub g: GnarkG2,
+    pub g_sigma_neg: GnarkG2,
+}
+
+/// Contents of a serialized `groth16.VerifyingKey`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GnarkVerifyingKey {
+    pub alpha1: GnarkG1,
+    pub beta1: GnarkG1,
+    pub beta2: GnarkG2,
+    pub gamma2: GnarkG2,
+    pub delta1: GnarkG1,
+    pub delta2: GnarkG2,
+    /// The IC points, the first one for the constant wire.
+    pub k: Vec<GnarkG1>,
+    /// For every commitment, the indices of the public wires it commits to.
+    pub public_and_commitment_committed: Vec<Vec<u64>>,
+    pub commitment_keys: Vec<GnarkCommitmentKey>,
+}
+
+/// Contents of a serialized `groth16.Proof`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GnarkProof {
+    pub ar: GnarkG1,
+    pub bs: GnarkG2,
+    pub krs: GnarkG1,
+    pub commitments: Vec<GnarkG1>,
+    pub commitment_pok: GnarkG1,
+}
+
+/// The public part of a serialized witness, in circuit order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GnarkPublicWitness(pub Vec<[u8; 32]>);
+
+impl GnarkVerifyingKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let mut reader = Reader(bytes);
+        let alpha1 = reader.g1()?;
+        let beta1 = reader.g1()?;
+        let beta2 = reader.g2()?;
+        let gamma2 = reader.g2()?;
+        let delta1 = reader.g1()?;
+        let delta2 = reader.g2()?;
+        let k = reader.vec(Reader::g1)?;
+        let public_and_commitment_committed = reader.vec(|reader| reader.vec(Reader::u64))?;
+        let commitment_keys = reader.vec(|reader| {
+            Ok(GnarkCommitmentKey {
+                g: reader.g2()?,
+                g_sigma_neg: reader.g2()?,
+            })
+        })?;
+        reader.finish()?;
+        Ok(GnarkVerifyingKey {
+            alpha1,
+            beta1,
+            beta2,
+            gamma2,
+            delta1,
+            delta2,
+            k,
+            public_and_commitment_committed,
+            commitment_keys,
+        })
+    }
+
+    // Commitments add public inputs derived from the proof, only `verify_committed` computes them
+    fn check_supported(&self) -> Result<(), VerifyError> {
+        if !self.commitment_keys.is_empty() || !self.public_and_commitment_committed.is_empty() {
+            return Err(VerifyError::Unsupported);
+        }
+        Ok(())
+    }
+}
+
+impl GnarkProof {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let mut reader = Reader(bytes);
+        let proof = GnarkProof {
+            ar: reader.g1()?,
+            bs: reader.g2()?,
+            krs: reader.g1()?,
+            commitments: reader.vec(Reader::g1)?,
+            commitment_pok: reader.g1()?,
+        };
+        reader.finish()?;
+        Ok(proof)
+    }
+
+    fn check_supported(&self) -> Result<(), VerifyError> {
+        if !self.commitments.is_empty() {
+            return Err(VerifyError::Unsupported);
+        }
+        Ok(())
+    }
+}
+
+impl GnarkPublicWitness {
+    /// Reads a witness exported with `witness.Public()`. Secret values are rejected, a full witness
+    /// has no place next to a proof.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let mut reader = Reader(bytes);
+        let n_public = reader.u32()?;
+        let n_secret = reader.u32()?;
+        let values = reader.vec(|reader| Ok(coordinate(reader.take(32)?)))?;
+        reader.finish()?;
+        if n_secret != 0 {
+            return Err(VerifyError::Unsupported);
+        }
+        if values.len() != n_public as usize {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        Ok(GnarkPublicWitness(values))
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], VerifyError> {
+        if self.0.len() < n {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, VerifyError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, VerifyError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    // No capacity up front, the length is untrusted and every element consumes input
+    fn vec<T>(
+        &mut self,
+        mut element: impl FnMut(&mut Self) -> Result<T, VerifyError>,
+    ) -> Result<Vec<T>, VerifyError> {
+        let len = self.u32()?;
+        let mut values = Vec::new();
+        for _ in 0..len {
+            values.push(element(self)?);
+        }
+        Ok(values)
+    }
+
+    fn g1(&mut self) -> Result<GnarkG1, VerifyError> {
+        let x = self.take(32)?;
+        let flag = x[0] & FLAG_MASK;
+        Ok(match flag {
+            FLAG_UNCOMPRESSED => GnarkG1::Uncompressed {
+                x: coordinate(x),
+                y: coordinate(self.take(32)?),
+            },
+            FLAG_COMPRESSED_INFINITY => {
+                check_infinity(x)?;
+                GnarkG1::CompressedInfinity
+            }
+            _ => GnarkG1::Compressed {
+                x: unflagged(x),
+                largest: flag == FLAG_COMPRESSED_LARGEST,
+            },
+        })
+    }
+
+    fn g2(&mut self) -> Result<GnarkG2, VerifyError> {
+        let x = self.take(64)?;
+        let flag = x[0] & FLAG_MASK;
+        let x_c1 = unflagged(&x[..32]);
+        let x_c0 = coordinate(&x[32..]);
+        Ok(match flag {
+            FLAG_UNCOMPRESSED => {
+                let y = self.take(64)?;
+                GnarkG2::Uncompressed {
+                    x: [x_c0, x_c1],
+                    y: [coordinate(&y[32..]), coordinate(&y[..32])],
+                }
+            }
+            FLAG_COMPRESSED_INFINITY => {
+                check_infinity(x)?;
+                GnarkG2::CompressedInfinity
+            }
+            _ => GnarkG2::Compressed {
+                x: [x_c0, x_c1],
+                largest: flag == FLAG_COMPRESSED_LARGEST,
+            },
+        })
+    }
+
+    fn finish(self) -> Result<(), VerifyError> {
+        if !self.0.is_empty() {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        Ok(())
+    }
+}
+
+fn coordinate(bytes: &[u8]) -> Coordinate {
+    let mut coordinate = [0u8; 32];
+    coordinate.copy_from_slice(bytes);
+    coordinate
+}
+
+fn unflagged(bytes: &[u8]) -> Coordinate {
+    let mut coordinate = coordinate(bytes);
+    coordinate[0] &= !FLAG_MASK;
+    coordinate
+}
+
+fn check_infinity(bytes: &[u8]) -> Result<(), VerifyError> {
+    if bytes[0] != FLAG_COMPRESSED_INFINITY || bytes[1..].iter().any(|&b| b != 0) {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    Ok(())
+}
+
+// Recovers `y` from `x` on the backend's own field arithmetic. `x` is range checked, a missing
+// square root means `x` is not on the curve.
+trait Decompress: Bn254Backend {
+    fn g1_decompress(x: &Coordinate, largest: bool) -> Result<Self::G1, VerifyError>;
+
+    fn g2_decompress(x: &[Coordinate; 2], largest: bool) -> Result<Self::G2, VerifyError>;
+}
+
+fn g1<B: Decompress>(p: &GnarkG1) -> Result<B::G1, VerifyError> {
+    match p {
+        GnarkG1::Uncompressed { x, y } => {
+            let mut bytes = [0u8; G1_BYTES];
+            bytes[..32].copy_from_slice(x);
+            bytes[32..].copy_from_slice(y);
+            B::g1_from_bytes(&bytes)
+        }
+        GnarkG1::Compressed { x, largest } => B::g1_decompress(x, *largest),
+        GnarkG1::CompressedInfinity => B::g1_from_bytes(&[0; G1_BYTES]),
+    }
+}
+
+fn g2<B: Decompress>(p: &GnarkG2) -> Result<B::G2, VerifyError> {
+    match p {
+        GnarkG2::Uncompressed { x, y } => {
+            let mut bytes = [0u8; G2_BYTES];
+            for (chunk, c) in bytes.chunks_mut(32).zip([&x[0], &x[1], &y[0], &y[1]]) {
+                chunk.copy_from_slice(c);
+            }
+            B::g2_from_bytes(&bytes)
+        }
+        GnarkG2::Compressed { x, largest } => B::g2_decompress(x, *largest),
+        GnarkG2::CompressedInfinity => B::g2_from_bytes(&[0; G2_BYTES]),
+    }
+}
+
+fn verifying_key<B: Decompress>(
+    vk: &GnarkVerifyingKey,
+) -> Result<types::VerifyingKey<B::G1, B::G2>, VerifyError> {
+    vk.check_supported()?;
+    key_points::<B>(vk)
+}
+
+fn key_points<B: Decompress>(
+    vk: &GnarkVerifyingKey,
+) -> Result<types::VerifyingKey<B::G1, B::G2>, VerifyError> {
+    Ok(types::VerifyingKey {
+        alpha1: g1::<B>(&vk.alpha1)?,
+        beta2: g2::<B>(&vk.beta2)?,
+        gamma2: g2::<B>(&vk.gamma2)?,
+        delta2: g2::<B>(&vk.delta2)?,
+        ic: vk.k.iter().map(g1::<B>).collect::<Result<_, _>>()?,
+    })
+}
+
+fn committed_verifying_key<B: Decompress>(
+    vk: &GnarkVerifyingKey,
+) -> Result<types::CommittedVerifyingKey<B::G1, B::G2>, VerifyError> {
+    Ok(types::CommittedVerifyingKey {
+        vk: key_points::<B>(vk)?,
+        public_committed: vk
+            .public_and_commitment_committed
+            .iter()
+            .map(|committed| {
+                committed
+                    .iter()
+                    .map(|&index| usize::try_from(index).map_err(|_| VerifyError::InvalidEncoding))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?,
+        commitment_keys: vk
+            .commitment_keys
+            .iter()
+            .map(|key| {
+                Ok(types::CommitmentKey {
+                    g: g2::<B>(&key.g)?,
+                    g_sigma_neg: g2::<B>(&key.g_sigma_neg)?,
+                })
+            })
+            .collect::<Result<_, VerifyError>>()?,
+    })
+}
+
+fn proof<B: Decompress>(proof: &GnarkProof) -> Result<types::Proof<B::G1, B::G2>, VerifyError> {
+    proof.check_supported()?;
+    proof_points::<B>(proof)
+}
+
+fn proof_points<B: Decompress>(
+    proof: &GnarkProof,
+) -> Result<types::Proof<B::G1, B::G2>, VerifyError> {
+    Ok(types::Proof {
+        a: g1::<B>(&proof.ar)?,
+        b: g2::<B>(&proof.bs)?,
+        c: g1::<B>(&proof.krs)?,
+    })
+}
+
+fn committed_proof<B: Decompress>(
+    proof: &GnarkProof,
+) -> Result<types::CommittedProof<B::G1, B::G2>, VerifyError> {
+    Ok(types::CommittedProof {
+        proof: proof_points::<B>(proof)?,
+        commitments: proof
+            .commitments
+            .iter()
+            .map(g1::<B>)
+            .collect::<Result<_, _>>()?,
+        commitment_pok: g1::<B>(&proof.commitment_pok)?,
+    })
+}
+
+#[cfg(feature = "zeropool")]
+mod zeropool {
+    use ::bn::{Fq, Fq2};
+
+    use super::{Coordinate, Decompress};
+    use crate::bn::{fq_from_u256, fq_to_u256, G1Point, G2Point, Zeropool};
+    use crate::VerifyError;
+    use casper_types::U256;
+
+    // The twist coefficient 3 / (9 + u)
+    const TWIST_B: [&str; 2] = [
+        "19485874751759354771024239261021720505790618469301721065564631296452457478373",
+        "266929791119991161246907387137283842545076965332900288569378510910307636690",
+    ];
+
+    fn fq(x: &Coordinate) -> Result<Fq, VerifyError> {
+        fq_from_u256(U256::from_big_endian(x))
+    }
+
+    fn is_largest(y: Fq) -> bool {
+        fq_to_u256(y) > fq_to_u256(-y)
+    }
+
+    impl Decompress for Zeropool {
+        fn g1_decompress(x: &Coordinate, largest: bool) -> Result<G1Point, VerifyError> {
+            let x = fq(x)?;
+            let b = Fq::one() + Fq::one() + Fq::one();
+            let y = (x * x * x + b).sqrt().ok_or(VerifyError::PointNotOnCurve)?;
+            let y = if is_largest(y) == largest { y } else { -y };
+            Ok(G1Point::new(fq_to_u256(x), fq_to_u256(y)))
+        }
+
+        fn g2_decompress(x: &[Coordinate; 2], largest: bool) -> Result<G2Point, VerifyError> {
+            let x = Fq2::new(fq(&x[0])?, fq(&x[1])?);
+            let b = Fq2::new(
+                Fq::from_str(TWIST_B[0]).expect("constant is in the field"),
+                Fq::from_str(TWIST_B[1]).expect("constant is in the field"),
+            );
+            let y = (x * x * x + b).sqrt().ok_or(VerifyError::PointNotOnCurve)?;
+            let y_largest = if y.imaginary().is_zero() {
+                is_largest(y.real())
+            } else {
+                is_largest(y.imaginary())
+            };
+            let y = if y_largest == largest { y } else { -y };
+            Ok(G2Point::new(
+                [fq_to_u256(x.real()), fq_to_u256(x.imaginary())],
+                [fq_to_u256(y.real()), fq_to_u256(y.imaginary())],
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "ark")]
+mod arkworks {
+    use ark_bn254::{g1, g2, Fq, Fq2, G1Affine, G2Affine};
+    use ark_ec::short_weierstrass::SWCurveConfig;
+    use ark_ff::{BigInteger, Field, PrimeField, Zero};
+
+    use super::{Coordinate, Decompress};
+    use crate::bn254::Arkworks;
+    use crate::VerifyError;
+
+    fn fq(x: &Coordinate) -> Result<Fq, VerifyError> {
+        let value = Fq::from_be_bytes_mod_order(x);
+        if value.into_bigint().to_bytes_be() != x {
+            return Err(VerifyError::CoordinateNotInField);
+        }
+        Ok(value)
+    }
+
+    fn is_largest(y: Fq) -> bool {
+        y.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO
+    }
+
+    impl Decompress for Arkworks {
+        fn g1_decompress(x: &Coordinate, largest: bool) -> Result<G1Affine, VerifyError> {
+            let x = fq(x)?;
+            let y = (x * x * x + g1::Config::COEFF_B)
+                .sqrt()
+                .ok_or(VerifyError::PointNotOnCurve)?;
+            let y = if is_largest(y) == largest { y } else { -y };
+            Ok(G1Affine::new_unchecked(x, y))
+        }
+
+        fn g2_decompress(x: &[Coordinate; 2], largest: bool) -> Result<G2Affine, VerifyError> {
+            let x = Fq2::new(fq(&x[0])?, fq(&x[1])?);
+            let y = (x * x * x + g2::Config::COEFF_B)
+                .sqrt()
+                .ok_or(VerifyError::PointNotOnCurve)?;
+            let y_largest = if y.c1.is_zero() {
+                is_largest(y.c0)
+            } else {
+                is_largest(y.c1)
+            };
+            let y = if y_largest == largest { y } else { -y };
+            Ok(G2Affine::new_unchecked(x, y))
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&GnarkVerifyingKey> for bn::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &GnarkVerifyingKey) -> Result<Self, Self::Error> {
+        verifying_key::<Zeropool>(vk)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&GnarkProof> for bn::Proof {
+    type Error = VerifyError;
+
+    fn try_from(gnark: &GnarkProof) -> Result<Self, Self::Error> {
+        proof::<Zeropool>(gnark)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&GnarkVerifyingKey> for bn::CommittedVerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &GnarkVerifyingKey) -> Result<Self, Self::Error> {
+        committed_verifying_key::<Zeropool>(vk)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&GnarkProof> for bn::CommittedProof {
+    type Error = VerifyError;
+
+    fn try_from(gnark: &GnarkProof) -> Result<Self, Self::Error> {
+        committed_proof::<Zeropool>(gnark)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&GnarkPublicWitness> for bn::PublicInputs {
+    fn from(witness: &GnarkPublicWitness) -> Self {
+        types::PublicInputs(
+            witness
+                .0
+                .iter()
+                .map(|value| U256::from_big_endian(value))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&GnarkVerifyingKey> for bn254::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &GnarkVerifyingKey) -> Result<Self, Self::Error> {
+        verifying_key::<Arkworks>(vk)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&GnarkProof> for bn254::Proof {
+    type Error = VerifyError;
+
+    fn try_from(gnark: &GnarkProof) -> Result<Self, Self::Error> {
+        proof::<Arkworks>(gnark)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&GnarkVerifyingKey> for bn254::CommittedVerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &GnarkVerifyingKey) -> Result<Self, Self::Error> {
+        committed_verifying_key::<Arkworks>(vk)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&GnarkProof> for bn254::CommittedProof {
+    type Error = VerifyError;
+
+    fn try_from(gnark: &GnarkProof) -> Result<Self, Self::Error> {
+        committed_proof::<Arkworks>(gnark)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl From<&GnarkPublicWitness> for bn254::PublicInputs {
+    fn from(witness: &GnarkPublicWitness) -> Self {
+        types::PublicInputs(
+            witness
+                .0
+                .iter()
+                .map(|value| BigUint::from_bytes_be(value))
+                .collect(),
+        )
+    }
+}
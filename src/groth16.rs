@@ -0,0 +1,300 @@
+// Groth16 verification written once against `Bn254Backend`. The `bn` and `bn254` modules implement
+// the trait for their curve libraries and the Casper host functions, and wrap these functions with
+// their own public input types.
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, Mul};
+
+use crate::{
+    batch::{self, Transcript},
+    commitment,
+    types::{CommittedProof, CommittedVerifyingKey, Proof, VerifyOptions, VerifyingKey},
+    VerifyError,
+};
+
+pub const G1_BYTES: usize = 64;
+pub const G2_BYTES: usize = 128;
+pub const SCALAR_BYTES: usize = 32;
+
+/// The BN254 operations Groth16 verification needs.
+///
+/// Points are serialized as 32 byte big-endian coordinates, `x || y` for G1 and
+/// `x.c0 || x.c1 || y.c0 || y.c1` for G2, with all zero bytes encoding the point at infinity.
+/// Backends decode that encoding to the identity of their group and encode the identity as zeros,
+/// no other coordinates stand for infinity.
+pub trait Bn254Backend {
+    type G1: Clone;
+    type G2: Clone;
+    /// An element of the scalar field.
+    type Scalar: Copy + Add<Output = Self::Scalar> + Mul<Output = Self::Scalar>;
+
+    fn g1_add(p: &Self::G1, q: &Self::G1) -> Result<Self::G1, VerifyError>;
+
+    fn g1_mul(p: &Self::G1, k: &Self::Scalar) -> Result<Self::G1, VerifyError>;
+
+    fn g1_neg(p: &Self::G1) -> Result<Self::G1, VerifyError>;
+
+    /// Returns whether `prod e(g1_i, g2_i) == 1`.
+    fn pairing_check(pairs: &[(Self::G1, Self::G2)]) -> Result<bool, VerifyError>;
+
+    /// Checks that `p` is on the curve. Backends that check every point they operate on may only
+    /// check the encoding here.
+    fn validate_g1(p: &Self::G1) -> Result<(), VerifyError>;
+
+    /// Checks that `p` is on the twist and in the prime-order subgroup, see `validate_g1`.
+    fn validate_g2(p: &Self::G2) -> Result<(), VerifyError>;
+
+    /// Decodes a point, rejecting coordinates outside the base field. Curve membership is left to
+    /// `validate_g1`.
+    fn g1_from_bytes(bytes: &[u8; G1_BYTES]) -> Result<Self::G1, VerifyError>;
+
+    fn g1_to_bytes(p: &Self::G1) -> [u8; G1_BYTES];
+
+    /// Decodes a point, rejecting coordinates outside the base field. Curve and subgroup membership
+    /// is left to `validate_g2`.
+    fn g2_from_bytes(bytes: &[u8; G2_BYTES]) -> Result<Self::G2, VerifyError>;
+
+    fn g2_to_bytes(p: &Self::G2) -> [u8; G2_BYTES];
+
+    fn scalar_zero() -> Self::Scalar;
+
+    /// Decodes a big-endian scalar, rejecting values `>= SCALAR_FIELD_MODULUS`.
+    fn scalar_from_bytes(bytes: &[u8; SCALAR_BYTES]) -> Result<Self::Scalar, VerifyError>;
+
+    fn scalar_to_bytes(k: &Self::Scalar) -> [u8; SCALAR_BYTES];
+}
+
+/// Swaps the Fq2 coefficients of an encoded G2 point, converting between the `Bn254Backend` order
+/// `x.c0 || x.c1 || y.c0 || y.c1` and the EIP-197 order `x.c1 || x.c0 || y.c1 || y.c0` in either
+/// direction.
+pub fn swap_fq2_coefficients(bytes: &[u8; G2_BYTES]) -> [u8; G2_BYTES] {
+    let mut swapped = [0u8; G2_BYTES];
+    for (target, source) in swapped.chunks_mut(64).zip(bytes.chunks(64)) {
+        target[..32].copy_from_slice(&source[32..]);
+        target[32..].copy_from_slice(&source[..32]);
+    }
+    swapped
+}
+
+/// A proof with its public inputs as scalars.
+pub type BatchEntry<B> = (
+    Proof<<B as Bn254Backend>::G1, <B as Bn254Backend>::G2>,
+    Vec<<B as Bn254Backend>::Scalar>,
+);
+
+/// Computes `vk_x = ic[0] + sum(inputs[i] * ic[i + 1])`.
+pub fn compute_vk_x<B: Bn254Backend>(
+    ic: &[B::G1],
+    inputs: &[B::Scalar],
+) -> Result<B::G1, VerifyError> {
+    if ic.is_empty() {
+        return Err(VerifyError::EmptyVerifyingKey);
+    }
+    if ic.len() != inputs.len() + 1 {
+        return Err(VerifyError::InputCountMismatch {
+            expected: ic.len() - 1,
+            got: inputs.len(),
+        });
+    }
+    let mut vk_x = ic[0].clone();
+    for (ic, input) in ic[1..].iter().zip(inputs) {
+        vk_x = B::g1_add(&vk_x, &B::g1_mul(ic, input)?)?;
+    }
+    Ok(vk_x)
+}
+
+pub fn validate_vk<B: Bn254Backend>(vk: &VerifyingKey<B::G1, B::G2>) -> Result<(), VerifyError> {
+    B::validate_g1(&vk.alpha1)?;
+    B::validate_g2(&vk.beta2)?;
+    B::validate_g2(&vk.gamma2)?;
+    B::validate_g2(&vk.delta2)?;
+    vk.ic.iter().try_for_each(B::validate_g1)
+}
+
+/// Checks that `A`, `B` and `C` are valid points other than the identity. An honest prover never
+/// outputs the identity, and with `A` or `B` at infinity their pairing drops out of the check.
+pub(crate) fn validate_proof<B: Bn254Backend>(
+    proof: &Proof<B::G1, B::G2>,
+) -> Result<(), VerifyError> {
+    B::validate_g1(&proof.a)?;
+    B::validate_g2(&proof.b)?;
+    B::validate_g1(&proof.c)?;
+    if B::g1_to_bytes(&proof.a) == [0; G1_BYTES]
+        || B::g2_to_bytes(&proof.b) == [0; G2_BYTES]
+        || B::g1_to_bytes(&proof.c) == [0; G1_BYTES]
+    {
+        return Err(VerifyError::PointAtInfinity);
+    }
+    Ok(())
+}
+
+/// Checks `e(-A, B) * e(alpha1, beta2) * e(vk_x, gamma2) * e(C, delta2) == 1`.
+///
+/// `inputs` are scalar field elements, `options.inputs` is applied by the caller when converting
+/// public signals to scalars.
+pub fn verify<B: Bn254Backend>(
+    vk: &VerifyingKey<B::G1, B::G2>,
+    proof: &Proof<B::G1, B::G2>,
+    inputs: &[B::Scalar],
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    validate_proof::<B>(proof)?;
+    if options.validate_vk {
+        validate_vk::<B>(vk)?;
+    }
+    let vk_x = compute_vk_x::<B>(&vk.ic, inputs)?;
+    check_pairing::<B>(vk, proof, vk_x)
+}
+
+fn check_pairing<B: Bn254Backend>(
+    vk: &VerifyingKey<B::G1, B::G2>,
+    proof: &Proof<B::G1, B::G2>,
+    vk_x: B::G1,
+) -> Result<bool, VerifyError> {
+    B::pairing_check(&[
+        (B::g1_neg(&proof.a)?, proof.b.clone()),
+        (vk.alpha1.clone(), vk.beta2.clone()),
+        (vk_x, vk.gamma2.clone()),
+        (proof.c.clone(), vk.delta2.clone()),
+    ])
+}
+
+/// Verifies a proof with gnark's Pedersen commitments. Every commitment adds a public input,
+/// hashed from the commitment and the public inputs it commits to, and is itself added to `vk_x`.
+/// The batched proof of knowledge of the commitment openings is checked by a second pairing check,
+/// see `commitment::check_pok`. Without commitments this is `verify`.
+pub fn verify_committed<B: Bn254Backend>(
+    vk: &CommittedVerifyingKey<B::G1, B::G2>,
+    proof: &CommittedProof<B::G1, B::G2>,
+    inputs: &[B::Scalar],
+    options: &VerifyOptions,
+) -> Result<bool, VerifyError> {
+    validate_proof::<B>(&proof.proof)?;
+    proof.commitments.iter().try_for_each(B::validate_g1)?;
+    B::validate_g1(&proof.commitment_pok)?;
+    if options.validate_vk {
+        validate_vk::<B>(&vk.vk)?;
+        for key in &vk.commitment_keys {
+            B::validate_g2(&key.g)?;
+            B::validate_g2(&key.g_sigma_neg)?;
+        }
+    }
+    let n = vk.commitment_keys.len();
+    if vk.public_committed.len() != n {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    if proof.commitments.len() != n {
+        return Err(VerifyError::CommitmentCountMismatch {
+            expected: n,
+            got: proof.commitments.len(),
+        });
+    }
+    if vk.vk.ic.is_empty() {
+        return Err(VerifyError::EmptyVerifyingKey);
+    }
+    if vk.vk.ic.len() != inputs.len() + n + 1 {
+        return Err(VerifyError::InputCountMismatch {
+            expected: vk.vk.ic.len().saturating_sub(n + 1),
+            got: inputs.len(),
+        });
+    }
+
+    let commitment_inputs =
+        commitment::commitment_inputs::<B>(&vk.public_committed, &proof.commitments, inputs)?;
+    if !commitment::check_pok::<B>(
+        &vk.commitment_keys,
+        &proof.commitments,
+        &commitment_inputs,
+        &proof.commitment_pok,
+    )? {
+        return Ok(false);
+    }
+    let inputs = [inputs, &commitment_inputs].concat();
+    let mut vk_x = compute_vk_x::<B>(&vk.vk.ic, &inputs)?;
+    for commitment in &proof.commitments {
+        vk_x = B::g1_add(&vk_x, commitment)?;
+    }
+    check_pairing::<B>(&vk.vk, &proof.proof, vk_x)
+}
+
+/// Checks `prod e(r_i A_i, B_i) * e(-sum(r_i) alpha1, beta2) * e(-sum(r_i vk_x_i), gamma2)
+/// * e(-sum(r_i C_i), delta2) == 1` for scalars `r_i` drawn from a transcript of the whole batch.
+///
+/// Returns `Ok(None)` if every proof is valid, otherwise `Ok(Some(i))` with the index of the first
+/// invalid proof, found by bisecting the rejected batch. Malformed points or inputs fail the whole
+/// call with the same error `verify` returns for them.
+pub fn verify_batch<B: Bn254Backend>(
+    vk: &VerifyingKey<B::G1, B::G2>,
+    batch: &[BatchEntry<B>],
+    options: &VerifyOptions,
+) -> Result<Option<usize>, VerifyError> {
+    if options.validate_vk {
+        validate_vk::<B>(vk)?;
+    }
+    if vk.ic.is_empty() {
+        return Err(VerifyError::EmptyVerifyingKey);
+    }
+    let mut transcript = Transcript::new();
+    transcript.append(&B::g1_to_bytes(&vk.alpha1));
+    for g2 in [&vk.beta2, &vk.gamma2, &vk.delta2] {
+        transcript.append(&B::g2_to_bytes(g2));
+    }
+    for ic in &vk.ic {
+        transcript.append(&B::g1_to_bytes(ic));
+    }
+    for (proof, inputs) in batch {
+        validate_proof::<B>(proof)?;
+        if vk.ic.len() != inputs.len() + 1 {
+            return Err(VerifyError::InputCountMismatch {
+                expected: vk.ic.len() - 1,
+                got: inputs.len(),
+            });
+        }
+        transcript.append(&B::g1_to_bytes(&proof.a));
+        transcript.append(&B::g2_to_bytes(&proof.b));
+        transcript.append(&B::g1_to_bytes(&proof.c));
+        for input in inputs {
+            transcript.append(&B::scalar_to_bytes(input));
+        }
+    }
+    let scalars = transcript
+        .challenge_scalars(batch.len())
+        .into_iter()
+        .map(|challenge| {
+            let mut bytes = [0u8; SCALAR_BYTES];
+            bytes[SCALAR_BYTES - challenge.len()..].copy_from_slice(&challenge);
+            B::scalar_from_bytes(&bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    batch::first_invalid(0..batch.len(), &mut |range| {
+        let mut pairs = Vec::with_capacity(range.len() + 3);
+        let mut ic_scalars = vec![B::scalar_zero(); vk.ic.len()];
+        let mut c_sum: Option<B::G1> = None;
+        for i in range {
+            let (proof, inputs) = &batch[i];
+            let r = scalars[i];
+            pairs.push((B::g1_mul(&proof.a, &r)?, proof.b.clone()));
+            let c = B::g1_mul(&proof.c, &r)?;
+            c_sum = Some(match c_sum {
+                Some(sum) => B::g1_add(&sum, &c)?,
+                None => c,
+            });
+            ic_scalars[0] = ic_scalars[0] + r;
+            for (scalar, input) in ic_scalars[1..].iter_mut().zip(inputs) {
+                *scalar = *scalar + r * *input;
+            }
+        }
+
+        // sum(r_i vk_x_i) = sum_j (sum_i r_i inputs_i[j]) ic[j], one multiplication per IC point
+        let mut vk_x = B::g1_mul(&vk.ic[0], &ic_scalars[0])?;
+        for (ic, scalar) in vk.ic[1..].iter().zip(&ic_scalars[1..]) {
+            vk_x = B::g1_add(&vk_x, &B::g1_mul(ic, scalar)?)?;
+        }
+        let alpha = B::g1_mul(&vk.alpha1, &ic_scalars[0])?;
+        let c_sum = c_sum.expect("bisection never checks an empty range");
+        for (p, q) in [(alpha, &vk.beta2), (vk_x, &vk.gamma2), (c_sum, &vk.delta2)] {
+            pairs.push((B::g1_neg(&p)?, q.clone()));
+        }
+        B::pairing_check(&pairs)
+    })
+}
@@ -1,4 +1,35 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(not(any(feature = "ark", feature = "zeropool", feature = "casper")))]
+compile_error!("enable at least one backend feature: `ark`, `zeropool` or `casper`");
+
+#[cfg(feature = "ark-groth16")]
+pub mod ark_groth16;
+mod batch;
+#[cfg(feature = "zeropool")]
 pub mod bn;
+#[cfg(feature = "ark")]
 pub mod bn254;
+#[cfg(feature = "zeropool")]
+pub mod bytesrepr;
+pub mod commitment;
+mod error;
+pub mod gnark;
+pub mod groth16;
+pub mod precompile;
+#[cfg(feature = "std")]
+pub mod snarkjs;
+pub mod solidity;
+pub mod types;
+pub mod zkey;
+
+pub use error::VerifyError;
+
 pub const BASE_FIELD_MODULUS: &str =
     "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+pub const SCALAR_FIELD_MODULUS: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
@@ -0,0 +1,95 @@
+// The Ethereum bn256 precompiles, byte for byte: `ECADD` (0x06) and `ECMUL` (0x07) from EIP-196,
+// `ECPAIRING` (0x08) from EIP-197.
+//
+// Inputs shorter than the expected length are padded with zeros on the right and extra bytes are
+// ignored, except for `ecpairing` which requires a multiple of 192 bytes. Coordinates are 32 byte
+// big-endian and must be smaller than `BASE_FIELD_MODULUS`, `(0, 0)` encodes the point at
+// infinity. G2 coordinates are given imaginary part first, `x.c1 || x.c0 || y.c1 || y.c0`.
+use alloc::vec::Vec;
+
+use crate::{
+    groth16::{swap_fq2_coefficients, Bn254Backend, G1_BYTES, G2_BYTES, SCALAR_BYTES},
+    VerifyError,
+};
+
+pub const ECADD_INPUT_LENGTH: usize = 2 * G1_BYTES;
+pub const ECMUL_INPUT_LENGTH: usize = G1_BYTES + SCALAR_BYTES;
+pub const ECPAIRING_PAIR_LENGTH: usize = G1_BYTES + G2_BYTES;
+
+// SCALAR_FIELD_MODULUS big-endian
+const SCALAR_FIELD_MODULUS_BYTES: [u8; SCALAR_BYTES] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Copies `input` into a zero padded buffer of `N` bytes, dropping anything past `N`.
+fn padded<const N: usize>(input: &[u8]) -> [u8; N] {
+    let mut buf = [0u8; N];
+    let len = input.len().min(N);
+    buf[..len].copy_from_slice(&input[..len]);
+    buf
+}
+
+/// Reduces a 256 bit big-endian integer modulo `SCALAR_FIELD_MODULUS`. G1 has prime order, so
+/// multiplying by the reduced scalar gives the same point.
+fn reduce_scalar(mut k: [u8; SCALAR_BYTES]) -> [u8; SCALAR_BYTES] {
+    // 2^256 < 6r, at most five subtractions
+    while k >= SCALAR_FIELD_MODULUS_BYTES {
+        let mut borrow = 0u16;
+        for (byte, r) in k.iter_mut().zip(SCALAR_FIELD_MODULUS_BYTES).rev() {
+            let difference = 0x100 + *byte as u16 - r as u16 - borrow;
+            *byte = difference as u8;
+            borrow = u16::from(difference < 0x100);
+        }
+    }
+    k
+}
+
+fn read_g1<B: Bn254Backend>(bytes: &[u8]) -> Result<B::G1, VerifyError> {
+    let p = B::g1_from_bytes(&padded(bytes))?;
+    B::validate_g1(&p)?;
+    Ok(p)
+}
+
+// EIP-197 orders Fq2 coefficients c1, c0
+fn read_g2<B: Bn254Backend>(bytes: &[u8]) -> Result<B::G2, VerifyError> {
+    let p = B::g2_from_bytes(&swap_fq2_coefficients(&padded(bytes)))?;
+    B::validate_g2(&p)?;
+    Ok(p)
+}
+
+/// `ECADD`: `x1 || y1 || x2 || y2` to `x || y`.
+pub fn ecadd<B: Bn254Backend>(input: &[u8]) -> Result<[u8; G1_BYTES], VerifyError> {
+    let input: [u8; ECADD_INPUT_LENGTH] = padded(input);
+    let p = read_g1::<B>(&input[..G1_BYTES])?;
+    let q = read_g1::<B>(&input[G1_BYTES..])?;
+    Ok(B::g1_to_bytes(&B::g1_add(&p, &q)?))
+}
+
+/// `ECMUL`: `x || y || k` to `x || y`. `k` may be any 256 bit integer.
+pub fn ecmul<B: Bn254Backend>(input: &[u8]) -> Result<[u8; G1_BYTES], VerifyError> {
+    let input: [u8; ECMUL_INPUT_LENGTH] = padded(input);
+    let p = read_g1::<B>(&input[..G1_BYTES])?;
+    let k = B::scalar_from_bytes(&reduce_scalar(padded(&input[G1_BYTES..])))?;
+    Ok(B::g1_to_bytes(&B::g1_mul(&p, &k)?))
+}
+
+/// `ECPAIRING`: `k` pairs of a G1 and a G2 point to `1` if `prod e(g1_i, g2_i) == 1` and `0`
+/// otherwise, as a 32 byte big-endian integer. Empty input succeeds with `1`.
+pub fn ecpairing<B: Bn254Backend>(input: &[u8]) -> Result<[u8; 32], VerifyError> {
+    if !input.len().is_multiple_of(ECPAIRING_PAIR_LENGTH) {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    let pairs = input
+        .chunks(ECPAIRING_PAIR_LENGTH)
+        .map(|pair| {
+            Ok((
+                read_g1::<B>(&pair[..G1_BYTES])?,
+                read_g2::<B>(&pair[G1_BYTES..])?,
+            ))
+        })
+        .collect::<Result<Vec<_>, VerifyError>>()?;
+    let mut output = [0u8; 32];
+    output[31] = u8::from(B::pairing_check(&pairs)?);
+    Ok(output)
+}
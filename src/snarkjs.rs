@@ -0,0 +1,266 @@
+// Loaders for the JSON files written by `snarkjs zkey export verificationkey` and `snarkjs groth16 prove`
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "ark")]
+use ark_bn254::{Fq2, G1Affine, G2Affine};
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+#[cfg(feature = "ark")]
+use num_bigint::BigUint;
+use serde::Deserialize;
+
+#[cfg(feature = "zeropool")]
+use crate::bn;
+#[cfg(feature = "ark")]
+use crate::bn254;
+use crate::{types, VerifyError};
+
+/// Projective G1 point `[x, y, z]` as decimal strings.
+pub type G1Json = [String; 3];
+/// Projective G2 point `[[x.c0, x.c1], [y.c0, y.c1], [z.c0, z.c1]]` as decimal strings.
+pub type G2Json = [[String; 2]; 3];
+
+/// Contents of `verification_key.json`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SnarkjsVerifyingKey {
+    pub protocol: String,
+    pub curve: String,
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub vk_alpha_1: G1Json,
+    pub vk_beta_2: G2Json,
+    pub vk_gamma_2: G2Json,
+    pub vk_delta_2: G2Json,
+    #[serde(rename = "IC")]
+    pub ic: Vec<G1Json>,
+}
+
+/// Contents of `proof.json`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SnarkjsProof {
+    pub protocol: String,
+    pub curve: String,
+    pub pi_a: G1Json,
+    pub pi_b: G2Json,
+    pub pi_c: G1Json,
+}
+
+/// Contents of `public.json`, the public signals in circuit order.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct SnarkjsPublicSignals(pub Vec<String>);
+
+impl SnarkjsVerifyingKey {
+    pub fn from_json(json: &str) -> Result<Self, VerifyError> {
+        let vk: Self = serde_json::from_str(json).map_err(|_| VerifyError::InvalidJson)?;
+        check_protocol(&vk.protocol, &vk.curve)?;
+        if vk.ic.len().checked_sub(1) != Some(vk.n_public) {
+            return Err(VerifyError::InputCountMismatch {
+                expected: vk.n_public,
+                got: vk.ic.len().saturating_sub(1),
+            });
+        }
+        Ok(vk)
+    }
+}
+
+impl SnarkjsProof {
+    pub fn from_json(json: &str) -> Result<Self, VerifyError> {
+        let proof: Self = serde_json::from_str(json).map_err(|_| VerifyError::InvalidJson)?;
+        check_protocol(&proof.protocol, &proof.curve)?;
+        Ok(proof)
+    }
+}
+
+impl SnarkjsPublicSignals {
+    pub fn from_json(json: &str) -> Result<Self, VerifyError> {
+        serde_json::from_str(json).map_err(|_| VerifyError::InvalidJson)
+    }
+}
+
+fn check_protocol(protocol: &str, curve: &str) -> Result<(), VerifyError> {
+    if protocol != "groth16" || curve != "bn128" {
+        return Err(VerifyError::Unsupported);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ark")]
+fn parse_decimal(value: &str) -> Result<BigUint, VerifyError> {
+    BigUint::parse_bytes(value.as_bytes(), 10).ok_or(VerifyError::InvalidEncoding)
+}
+
+// A small decimal without leading zeros, `z` coordinates are only ever 0 or 1
+fn small_decimal(value: &str) -> Result<&str, VerifyError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    Ok(value.trim_start_matches('0'))
+}
+
+// snarkjs always normalizes to z = 1, except for the point at infinity which has z = 0
+fn is_infinity(z: &str) -> Result<bool, VerifyError> {
+    match small_decimal(z)? {
+        "" => Ok(true),
+        "1" => Ok(false),
+        _ => Err(VerifyError::InvalidEncoding),
+    }
+}
+
+fn is_infinity2(z: &[String; 2]) -> Result<bool, VerifyError> {
+    if !small_decimal(&z[1])?.is_empty() {
+        return Err(VerifyError::InvalidEncoding);
+    }
+    is_infinity(&z[0])
+}
+
+#[cfg(feature = "zeropool")]
+fn u256_from_decimal(value: &str) -> Result<U256, VerifyError> {
+    U256::from_dec_str(value).map_err(|_| VerifyError::InvalidEncoding)
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_coordinate(value: &str) -> Result<U256, VerifyError> {
+    let coordinate = u256_from_decimal(value)?;
+    bn::fq_from_u256(coordinate)?;
+    Ok(coordinate)
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_g1(point: &G1Json) -> Result<bn::G1Point, VerifyError> {
+    if is_infinity(&point[2])? {
+        return Ok(bn::G1Point::new(U256::zero(), U256::zero()));
+    }
+    Ok(bn::G1Point::new(
+        bn_coordinate(&point[0])?,
+        bn_coordinate(&point[1])?,
+    ))
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_g2(point: &G2Json) -> Result<bn::G2Point, VerifyError> {
+    if is_infinity2(&point[2])? {
+        return Ok(bn::G2Point::from_snarkjs(
+            [U256::zero(); 2],
+            [U256::zero(); 2],
+        ));
+    }
+    Ok(bn::G2Point::from_snarkjs(
+        [bn_coordinate(&point[0][0])?, bn_coordinate(&point[0][1])?],
+        [bn_coordinate(&point[1][0])?, bn_coordinate(&point[1][1])?],
+    ))
+}
+
+#[cfg(feature = "ark")]
+fn bn254_g1(point: &G1Json) -> Result<G1Affine, VerifyError> {
+    if is_infinity(&point[2])? {
+        return Ok(G1Affine::identity());
+    }
+    Ok(bn254::g1_unchecked(
+        bn254::parse_biguint_to_fq(&point[0])?,
+        bn254::parse_biguint_to_fq(&point[1])?,
+    ))
+}
+
+#[cfg(feature = "ark")]
+fn bn254_fq2(value: &[String; 2]) -> Result<Fq2, VerifyError> {
+    Ok(Fq2::new(
+        bn254::parse_biguint_to_fq(&value[0])?,
+        bn254::parse_biguint_to_fq(&value[1])?,
+    ))
+}
+
+#[cfg(feature = "ark")]
+fn bn254_g2(point: &G2Json) -> Result<G2Affine, VerifyError> {
+    if is_infinity2(&point[2])? {
+        return Ok(G2Affine::identity());
+    }
+    Ok(bn254::g2_unchecked(
+        bn254_fq2(&point[0])?,
+        bn254_fq2(&point[1])?,
+    ))
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&SnarkjsVerifyingKey> for bn::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &SnarkjsVerifyingKey) -> Result<Self, Self::Error> {
+        Ok(types::VerifyingKey {
+            alpha1: bn_g1(&vk.vk_alpha_1)?,
+            beta2: bn_g2(&vk.vk_beta_2)?,
+            gamma2: bn_g2(&vk.vk_gamma_2)?,
+            delta2: bn_g2(&vk.vk_delta_2)?,
+            ic: vk.ic.iter().map(bn_g1).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&SnarkjsProof> for bn::Proof {
+    type Error = VerifyError;
+
+    fn try_from(proof: &SnarkjsProof) -> Result<Self, Self::Error> {
+        Ok(types::Proof {
+            a: bn_g1(&proof.pi_a)?,
+            b: bn_g2(&proof.pi_b)?,
+            c: bn_g1(&proof.pi_c)?,
+        })
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&SnarkjsPublicSignals> for bn::PublicInputs {
+    type Error = VerifyError;
+
+    fn try_from(signals: &SnarkjsPublicSignals) -> Result<Self, Self::Error> {
+        signals
+            .0
+            .iter()
+            .map(|signal| u256_from_decimal(signal))
+            .collect::<Result<Vec<_>, _>>()
+            .map(types::PublicInputs)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&SnarkjsVerifyingKey> for bn254::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &SnarkjsVerifyingKey) -> Result<Self, Self::Error> {
+        Ok(types::VerifyingKey {
+            alpha1: bn254_g1(&vk.vk_alpha_1)?,
+            beta2: bn254_g2(&vk.vk_beta_2)?,
+            gamma2: bn254_g2(&vk.vk_gamma_2)?,
+            delta2: bn254_g2(&vk.vk_delta_2)?,
+            ic: vk.ic.iter().map(bn254_g1).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&SnarkjsProof> for bn254::Proof {
+    type Error = VerifyError;
+
+    fn try_from(proof: &SnarkjsProof) -> Result<Self, Self::Error> {
+        Ok(types::Proof {
+            a: bn254_g1(&proof.pi_a)?,
+            b: bn254_g2(&proof.pi_b)?,
+            c: bn254_g1(&proof.pi_c)?,
+        })
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&SnarkjsPublicSignals> for bn254::PublicInputs {
+    type Error = VerifyError;
+
+    fn try_from(signals: &SnarkjsPublicSignals) -> Result<Self, Self::Error> {
+        signals
+            .0
+            .iter()
+            .map(|signal| parse_decimal(signal))
+            .collect::<Result<Vec<_>, _>>()
+            .map(types::PublicInputs)
+    }
+}
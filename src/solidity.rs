@@ -0,0 +1,322 @@
+// Interop with the Solidity verifiers generated by snarkjs (`snarkjs zkey export solidityverifier`)
+// and the EIP-197 based verifiers derived from them: ABI calldata of
+// `verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[N])` and the text printed by
+// `snarkjs zkey export soliditycalldata` (`generatecall`). Every argument is a static array, so the
+// calldata is the selector followed by `8 + N` big-endian words without offsets. `B` is written as
+// on the EVM, `[[x.c1, x.c0], [y.c1, y.c0]]`, the reverse of the `[c0, c1]` order used elsewhere.
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+#[cfg(feature = "ark")]
+use num_bigint::BigUint;
+use sha3::{Digest, Keccak256};
+
+#[cfg(feature = "zeropool")]
+use crate::bn::{self, Zeropool};
+#[cfg(feature = "ark")]
+use crate::bn254::{self, Arkworks};
+use crate::{
+    groth16::{swap_fq2_coefficients, Bn254Backend, G1_BYTES, G2_BYTES},
+    types, VerifyError,
+};
+
+/// A big-endian `uint256`, as one ABI word.
+pub type Word = [u8; 32];
+
+pub const SELECTOR_BYTES: usize = 4;
+pub const WORD_BYTES: usize = 32;
+// `a`, `b` and `c`
+const PROOF_WORDS: usize = 8;
+
+/// A proof as passed to `verifyProof`, with `b` in EVM order `[[x.c1, x.c0], [y.c1, y.c0]]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolidityProof {
+    pub a: [Word; 2],
+    pub b: [[Word; 2]; 2],
+    pub c: [Word; 2],
+}
+
+/// The public signals passed to `verifyProof`, in circuit order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoliditySignals(pub Vec<Word>);
+
+/// The arguments of one `verifyProof` call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolidityCalldata {
+    pub proof: SolidityProof,
+    pub public: SoliditySignals,
+}
+
+/// The 4 byte selector of a Solidity function, the start of `keccak256(signature)`.
+pub fn selector(signature: &str) -> [u8; SELECTOR_BYTES] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// The selector of `verifyProof` for a circuit with `n_public` public signals.
+pub fn verify_proof_selector(n_public: usize) -> [u8; SELECTOR_BYTES] {
+    selector(&format!(
+        "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[{n_public}])"
+    ))
+}
+
+impl SolidityCalldata {
+    /// Reads the calldata of a `verifyProof` call. The number of public signals follows from the
+    /// length and the selector has to match it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        if bytes.len() < SELECTOR_BYTES + PROOF_WORDS * WORD_BYTES
+            || !(bytes.len() - SELECTOR_BYTES).is_multiple_of(WORD_BYTES)
+        {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        let (selector, args) = bytes.split_at(SELECTOR_BYTES);
+        let words: Vec<Word> = args.chunks(WORD_BYTES).map(word).collect();
+        if selector != verify_proof_selector(words.len() - PROOF_WORDS) {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        Ok(Self::from_words(&words))
+    }
+
+    /// Encodes a `verifyProof` call, selector included.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = verify_proof_selector(self.public.0.len()).to_vec();
+        for word in self.words() {
+            bytes.extend_from_slice(&word);
+        }
+        bytes
+    }
+
+    /// Reads the output of `snarkjs zkey export soliditycalldata`,
+    /// `["0x..", "0x.."],[["0x..", "0x.."],["0x..", "0x.."]],["0x..", "0x.."],["0x..", ...]`.
+    pub fn from_generatecall(text: &str) -> Result<Self, VerifyError> {
+        // The brackets and commas with every quoted word replaced by `x`, compared against the
+        // shape for the number of words found
+        let mut shape = String::new();
+        let mut words = Vec::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '"' => {
+                    let end = rest.find('"').ok_or(VerifyError::InvalidEncoding)?;
+                    words.push(hex_word(&rest[..end])?);
+                    rest = &rest[end + 1..];
+                    shape.push('x');
+                }
+                c if c.is_whitespace() => {}
+                c => shape.push(c),
+            }
+        }
+        let n_public = words
+            .len()
+            .checked_sub(PROOF_WORDS)
+            .ok_or(VerifyError::InvalidEncoding)?;
+        if shape != generatecall_shape(n_public) {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        Ok(Self::from_words(&words))
+    }
+
+    /// Formats the arguments as `snarkjs zkey export soliditycalldata` does.
+    pub fn to_generatecall(&self) -> String {
+        let list = |words: &[Word]| {
+            let words: Vec<String> = words
+                .iter()
+                .map(|word| {
+                    let digits: String = word.iter().map(|b| format!("{b:02x}")).collect();
+                    format!("\"0x{digits}\"")
+                })
+                .collect();
+            format!("[{}]", words.join(", "))
+        };
+        let proof = &self.proof;
+        format!(
+            "{},[{},{}],{},{}",
+            list(&proof.a),
+            list(&proof.b[0]),
+            list(&proof.b[1]),
+            list(&proof.c),
+            list(&self.public.0)
+        )
+    }
+
+    // `words` holds at least `PROOF_WORDS`
+    fn from_words(words: &[Word]) -> Self {
+        SolidityCalldata {
+            proof: SolidityProof {
+                a: [words[0], words[1]],
+                b: [[words[2], words[3]], [words[4], words[5]]],
+                c: [words[6], words[7]],
+            },
+            public: SoliditySignals(words[PROOF_WORDS..].to_vec()),
+        }
+    }
+
+    fn words(&self) -> impl Iterator<Item = Word> + '_ {
+        let proof = &self.proof;
+        proof
+            .a
+            .into_iter()
+            .chain(proof.b.into_iter().flatten())
+            .chain(proof.c)
+            .chain(self.public.0.iter().copied())
+    }
+}
+
+fn word(bytes: &[u8]) -> Word {
+    let mut word = [0u8; WORD_BYTES];
+    word.copy_from_slice(bytes);
+    word
+}
+
+// `0x` followed by at most 64 hex digits, snarkjs pads to 64
+fn hex_word(text: &str) -> Result<Word, VerifyError> {
+    let digits = text
+        .strip_prefix("0x")
+        .filter(|digits| !digits.is_empty() && digits.len() <= 2 * WORD_BYTES)
+        .ok_or(VerifyError::InvalidEncoding)?;
+    let mut word = [0u8; WORD_BYTES];
+    for (i, c) in digits.chars().rev().enumerate() {
+        let nibble = c.to_digit(16).ok_or(VerifyError::InvalidEncoding)? as u8;
+        word[WORD_BYTES - 1 - i / 2] |= nibble << (4 * (i % 2));
+    }
+    Ok(word)
+}
+
+fn generatecall_shape(n_public: usize) -> String {
+    let public = ["x"].repeat(n_public).join(",");
+    format!("[x,x],[[x,x],[x,x]],[x,x],[{public}]")
+}
+
+fn proof<B: Bn254Backend>(
+    proof: &SolidityProof,
+) -> Result<types::Proof<B::G1, B::G2>, VerifyError> {
+    let g1 = |p: &[Word; 2]| {
+        let mut bytes = [0u8; G1_BYTES];
+        bytes[..32].copy_from_slice(&p[0]);
+        bytes[32..].copy_from_slice(&p[1]);
+        B::g1_from_bytes(&bytes)
+    };
+    let mut b = [0u8; G2_BYTES];
+    for (chunk, word) in b.chunks_mut(32).zip(proof.b.iter().flatten()) {
+        chunk.copy_from_slice(word);
+    }
+    Ok(types::Proof {
+        a: g1(&proof.a)?,
+        b: B::g2_from_bytes(&swap_fq2_coefficients(&b))?,
+        c: g1(&proof.c)?,
+    })
+}
+
+fn solidity_proof<B: Bn254Backend>(proof: &types::Proof<B::G1, B::G2>) -> SolidityProof {
+    let g1 = |p: &B::G1| {
+        let bytes = B::g1_to_bytes(p);
+        [word(&bytes[..32]), word(&bytes[32..])]
+    };
+    let b = swap_fq2_coefficients(&B::g2_to_bytes(&proof.b));
+    SolidityProof {
+        a: g1(&proof.a),
+        b: [
+            [word(&b[..32]), word(&b[32..64])],
+            [word(&b[64..96]), word(&b[96..])],
+        ],
+        c: g1(&proof.c),
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&SolidityProof> for bn::Proof {
+    type Error = VerifyError;
+
+    fn try_from(solidity: &SolidityProof) -> Result<Self, Self::Error> {
+        proof::<Zeropool>(solidity)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&bn::Proof> for SolidityProof {
+    fn from(proof: &bn::Proof) -> Self {
+        solidity_proof::<Zeropool>(proof)
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&SoliditySignals> for bn::PublicInputs {
+    fn from(public: &SoliditySignals) -> Self {
+        types::PublicInputs(
+            public
+                .0
+                .iter()
+                .map(|word| U256::from_big_endian(word))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl From<&bn::PublicInputs> for SoliditySignals {
+    fn from(inputs: &bn::PublicInputs) -> Self {
+        SoliditySignals(
+            inputs
+                .iter()
+                .map(|input| {
+                    let mut word = [0u8; WORD_BYTES];
+                    input.to_big_endian(&mut word);
+                    word
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&SolidityProof> for bn254::Proof {
+    type Error = VerifyError;
+
+    fn try_from(solidity: &SolidityProof) -> Result<Self, Self::Error> {
+        proof::<Arkworks>(solidity)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl From<&bn254::Proof> for SolidityProof {
+    fn from(proof: &bn254::Proof) -> Self {
+        solidity_proof::<Arkworks>(proof)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl From<&SoliditySignals> for bn254::PublicInputs {
+    fn from(public: &SoliditySignals) -> Self {
+        types::PublicInputs(
+            public
+                .0
+                .iter()
+                .map(|word| BigUint::from_bytes_be(word))
+                .collect(),
+        )
+    }
+}
+
+/// Inputs wider than a `uint256` are `ScalarOutOfRange`.
+#[cfg(feature = "ark")]
+impl TryFrom<&bn254::PublicInputs> for SoliditySignals {
+    type Error = VerifyError;
+
+    fn try_from(inputs: &bn254::PublicInputs) -> Result<Self, Self::Error> {
+        inputs
+            .iter()
+            .map(|input| {
+                let bytes = input.to_bytes_be();
+                if bytes.len() > WORD_BYTES {
+                    return Err(VerifyError::ScalarOutOfRange);
+                }
+                let mut word = [0u8; WORD_BYTES];
+                word[WORD_BYTES - bytes.len()..].copy_from_slice(&bytes);
+                Ok(word)
+            })
+            .collect::<Result<_, _>>()
+            .map(SoliditySignals)
+    }
+}
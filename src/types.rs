@@ -0,0 +1,111 @@
+// Backend-agnostic Groth16 containers, instantiated with concrete point types in `bn` and `bn254`
+use alloc::vec::Vec;
+
+/// A Groth16 proof `(A, B, C)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Proof<G1, G2> {
+    pub a: G1,
+    pub b: G2,
+    pub c: G1,
+}
+
+/// A Groth16 verifying key. `ic[0]` is the constant term, `ic[i]` is weighted by public input `i - 1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey<G1, G2> {
+    pub alpha1: G1,
+    pub beta2: G2,
+    pub gamma2: G2,
+    pub delta2: G2,
+    pub ic: Vec<G1>,
+}
+
+/// A Pedersen commitment key of gnark's commitment extension, `g` and `-sigma g` in G2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommitmentKey<G2> {
+    pub g: G2,
+    pub g_sigma_neg: G2,
+}
+
+/// A verifying key for a circuit that commits to part of its witness, as with gnark's `api.Commit`.
+/// `vk.ic` has one more point per commitment, after those of the public inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommittedVerifyingKey<G1, G2> {
+    pub vk: VerifyingKey<G1, G2>,
+    /// For every commitment, the public inputs hashed with it, numbered from 1.
+    pub public_committed: Vec<Vec<usize>>,
+    /// One key per commitment, all sharing `g`.
+    pub commitment_keys: Vec<CommitmentKey<G2>>,
+}
+
+/// A proof with its commitments and one proof of knowledge of their openings, batched over all
+/// commitments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommittedProof<G1, G2> {
+    pub proof: Proof<G1, G2>,
+    pub commitments: Vec<G1>,
+    pub commitment_pok: G1,
+}
+
+/// The public signals of a proof, in circuit order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublicInputs<S>(pub Vec<S>);
+
+impl<S> PublicInputs<S> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, S> {
+        self.0.iter()
+    }
+}
+
+impl<S> From<Vec<S>> for PublicInputs<S> {
+    fn from(inputs: Vec<S>) -> Self {
+        PublicInputs(inputs)
+    }
+}
+
+/// Checks a backend performs before evaluating the pairing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Check that every verifying key point is on the curve and in the prime-order subgroup.
+    /// Proof points are always checked, skip this only for keys validated when they were stored.
+    pub validate_vk: bool,
+    /// How public inputs outside the scalar field are treated.
+    pub inputs: InputPolicy,
+}
+
+/// Public inputs are elements of the scalar field, but arrive as unbounded integers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputPolicy {
+    /// Reject inputs `>= SCALAR_FIELD_MODULUS` with `ScalarOutOfRange`. Without this, `x` and
+    /// `x + r` verify the same proof, which breaks uniqueness of nullifiers and similar signals.
+    #[default]
+    RejectNonCanonical,
+    /// Reduce inputs modulo `SCALAR_FIELD_MODULUS`.
+    Reduce,
+}
+
+impl VerifyOptions {
+    /// Options for a verifying key that was validated ahead of time.
+    pub fn trusted_vk() -> Self {
+        VerifyOptions {
+            validate_vk: false,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        VerifyOptions {
+            validate_vk: true,
+            inputs: InputPolicy::default(),
+        }
+    }
+}
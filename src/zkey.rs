@@ -0,0 +1,301 @@
+// Loader for the verifying key in circom / snarkjs `.zkey` files. The file is the binary container
+// shared by snarkjs' formats: the magic `zkey`, a `u32` version and section count, then sections
+// of `(type: u32, size: u64, data)` in any order, all little-endian. Only the header (1), the
+// Groth16 header (2) and the IC points (3) are read, the proving key sections are skipped. Field
+// elements are stored little-endian in Montgomery form, `x * 2^256 mod p`.
+use alloc::vec::Vec;
+
+#[cfg(feature = "zeropool")]
+use crate::bn::{self, Zeropool};
+#[cfg(feature = "ark")]
+use crate::bn254::{self, Arkworks};
+use crate::{
+    groth16::{Bn254Backend, G1_BYTES, G2_BYTES},
+    types, VerifyError,
+};
+
+/// A little-endian base field element in Montgomery form, as it appears in the file.
+pub type Element = [u8; 32];
+
+const MAGIC: &[u8; 4] = b"zkey";
+const VERSION: u32 = 1;
+const SECTION_HEADER: u32 = 1;
+const SECTION_GROTH16_HEADER: u32 = 2;
+const SECTION_IC: u32 = 3;
+const PROTOCOL_GROTH16: u32 = 1;
+
+// Both moduli little-endian, as the Groth16 header stores them
+const BASE_FIELD_MODULUS_LE: [u8; 32] = [
+    0x47, 0xfd, 0x7c, 0xd8, 0x16, 0x8c, 0x20, 0x3c, 0x8d, 0xca, 0x71, 0x68, 0x91, 0x6a, 0x81, 0x97,
+    0x5d, 0x58, 0x81, 0x81, 0xb6, 0x45, 0x50, 0xb8, 0x29, 0xa0, 0x31, 0xe1, 0x72, 0x4e, 0x64, 0x30,
+];
+const SCALAR_FIELD_MODULUS_LE: [u8; 32] = [
+    0x01, 0x00, 0x00, 0xf0, 0x93, 0xf5, 0xe1, 0x43, 0x91, 0x70, 0xb9, 0x79, 0x48, 0xe8, 0x33, 0x28,
+    0x5d, 0x58, 0x81, 0x81, 0xb6, 0x45, 0x50, 0xb8, 0x29, 0xa0, 0x31, 0xe1, 0x72, 0x4e, 0x64, 0x30,
+];
+
+/// A G1 point as stored in a zkey, `[x, y]`. Both coordinates zero is the point at infinity.
+pub type ZkeyG1 = [Element; 2];
+
+/// A G2 point as stored in a zkey, `[x, y]` with coefficients ordered `[c0, c1]`.
+pub type ZkeyG2 = [[Element; 2]; 2];
+
+/// The verifying key part of a Groth16 `.zkey`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZkeyVerifyingKey {
+    pub n_vars: u32,
+    pub n_public: u32,
+    pub domain_size: u32,
+    pub alpha1: ZkeyG1,
+    pub beta1: ZkeyG1,
+    pub beta2: ZkeyG2,
+    pub gamma2: ZkeyG2,
+    pub delta1: ZkeyG1,
+    pub delta2: ZkeyG2,
+    pub ic: Vec<ZkeyG1>,
+}
+
+impl ZkeyVerifyingKey {
+    /// Reads the verifying key of a zkey written by `snarkjs groth16 setup` or `zkey contribute`.
+    /// Keys for other protocols or curves are `Unsupported`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != MAGIC {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        if reader.u32()? != VERSION {
+            return Err(VerifyError::Unsupported);
+        }
+        let mut header = None;
+        let mut groth16_header = None;
+        let mut ic = None;
+        for _ in 0..reader.u32()? {
+            let section = reader.u32()?;
+            let size = usize::try_from(reader.u64()?).map_err(|_| VerifyError::InvalidEncoding)?;
+            let data = reader.take(size)?;
+            let slot = match section {
+                SECTION_HEADER => &mut header,
+                SECTION_GROTH16_HEADER => &mut groth16_header,
+                SECTION_IC => &mut ic,
+                _ => continue,
+            };
+            if slot.replace(data).is_some() {
+                return Err(VerifyError::InvalidEncoding);
+            }
+        }
+        reader.finish()?;
+        let (Some(header), Some(groth16_header), Some(ic)) = (header, groth16_header, ic) else {
+            return Err(VerifyError::InvalidEncoding);
+        };
+
+        let mut header = Reader(header);
+        if header.u32()? != PROTOCOL_GROTH16 {
+            return Err(VerifyError::Unsupported);
+        }
+        header.finish()?;
+
+        let mut reader = Reader(groth16_header);
+        reader.modulus(&BASE_FIELD_MODULUS_LE)?;
+        reader.modulus(&SCALAR_FIELD_MODULUS_LE)?;
+        let n_vars = reader.u32()?;
+        let n_public = reader.u32()?;
+        let domain_size = reader.u32()?;
+        let alpha1 = reader.g1()?;
+        let beta1 = reader.g1()?;
+        let beta2 = reader.g2()?;
+        let gamma2 = reader.g2()?;
+        let delta1 = reader.g1()?;
+        let delta2 = reader.g2()?;
+        reader.finish()?;
+
+        // One point for the constant and one per public signal
+        if ic.len() % G1_BYTES != 0 {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        let mut reader = Reader(ic);
+        let ic = (0..ic.len() / G1_BYTES)
+            .map(|_| reader.g1())
+            .collect::<Result<Vec<_>, _>>()?;
+        if ic.len().checked_sub(1) != Some(n_public as usize) {
+            return Err(VerifyError::InputCountMismatch {
+                expected: n_public as usize,
+                got: ic.len().saturating_sub(1),
+            });
+        }
+
+        Ok(ZkeyVerifyingKey {
+            n_vars,
+            n_public,
+            domain_size,
+            alpha1,
+            beta1,
+            beta2,
+            gamma2,
+            delta1,
+            delta2,
+            ic,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], VerifyError> {
+        if self.0.len() < n {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, VerifyError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, VerifyError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    // A field size in bytes followed by the modulus, only BN254 is supported
+    fn modulus(&mut self, expected: &[u8; 32]) -> Result<(), VerifyError> {
+        let n8 = self.u32()? as usize;
+        if self.take(n8)? != expected {
+            return Err(VerifyError::Unsupported);
+        }
+        Ok(())
+    }
+
+    fn element(&mut self) -> Result<Element, VerifyError> {
+        let mut element = [0u8; 32];
+        element.copy_from_slice(self.take(32)?);
+        Ok(element)
+    }
+
+    fn g1(&mut self) -> Result<ZkeyG1, VerifyError> {
+        Ok([self.element()?, self.element()?])
+    }
+
+    fn g2(&mut self) -> Result<ZkeyG2, VerifyError> {
+        Ok([
+            [self.element()?, self.element()?],
+            [self.element()?, self.element()?],
+        ])
+    }
+
+    fn finish(self) -> Result<(), VerifyError> {
+        if !self.0.is_empty() {
+            return Err(VerifyError::InvalidEncoding);
+        }
+        Ok(())
+    }
+}
+
+// Converts an element out of Montgomery form into the big-endian coordinate of the backend's byte
+// encoding. Elements `>= p` are `CoordinateNotInField`.
+trait Montgomery: Bn254Backend {
+    fn from_montgomery(element: &Element) -> Result<[u8; 32], VerifyError>;
+}
+
+fn g1<B: Montgomery>(p: &ZkeyG1) -> Result<B::G1, VerifyError> {
+    let mut bytes = [0u8; G1_BYTES];
+    for (chunk, c) in bytes.chunks_mut(32).zip(p) {
+        chunk.copy_from_slice(&B::from_montgomery(c)?);
+    }
+    B::g1_from_bytes(&bytes)
+}
+
+fn g2<B: Montgomery>(p: &ZkeyG2) -> Result<B::G2, VerifyError> {
+    let mut bytes = [0u8; G2_BYTES];
+    for (chunk, c) in bytes.chunks_mut(32).zip(p.iter().flatten()) {
+        chunk.copy_from_slice(&B::from_montgomery(c)?);
+    }
+    B::g2_from_bytes(&bytes)
+}
+
+fn verifying_key<B: Montgomery>(
+    vk: &ZkeyVerifyingKey,
+) -> Result<types::VerifyingKey<B::G1, B::G2>, VerifyError> {
+    Ok(types::VerifyingKey {
+        alpha1: g1::<B>(&vk.alpha1)?,
+        beta2: g2::<B>(&vk.beta2)?,
+        gamma2: g2::<B>(&vk.gamma2)?,
+        delta2: g2::<B>(&vk.delta2)?,
+        ic: vk.ic.iter().map(g1::<B>).collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(feature = "zeropool")]
+mod zeropool {
+    use ::bn::Fq;
+    use casper_types::U256;
+
+    use super::{Element, Montgomery};
+    use crate::bn::{fq_from_u256, fq_to_u256, Zeropool};
+    use crate::VerifyError;
+
+    // 2^-256 mod p
+    const R_INV: &str =
+        "20988524275117001072002809824448087578619730785600314334253784976379291040311";
+
+    impl Montgomery for Zeropool {
+        fn from_montgomery(element: &Element) -> Result<[u8; 32], VerifyError> {
+            let value = fq_from_u256(U256::from_little_endian(element))?;
+            let r_inv = Fq::from_str(R_INV).expect("constant is in the field");
+            let mut bytes = [0u8; 32];
+            fq_to_u256(value * r_inv).to_big_endian(&mut bytes);
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "ark")]
+mod arkworks {
+    use ark_bn254::Fq;
+    use ark_ff::{BigInt, BigInteger, PrimeField};
+
+    use super::{Element, Montgomery};
+    use crate::bn254::Arkworks;
+    use crate::VerifyError;
+
+    impl Montgomery for Arkworks {
+        fn from_montgomery(element: &Element) -> Result<[u8; 32], VerifyError> {
+            let mut limbs = [0u64; 4];
+            for (limb, bytes) in limbs.iter_mut().zip(element.chunks(8)) {
+                let mut le = [0u8; 8];
+                le.copy_from_slice(bytes);
+                *limb = u64::from_le_bytes(le);
+            }
+            let montgomery = BigInt::new(limbs);
+            if montgomery >= Fq::MODULUS {
+                return Err(VerifyError::CoordinateNotInField);
+            }
+            // arkworks keeps `Fq` in Montgomery form with the same `R`, so the limbs are the element
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&Fq::new_unchecked(montgomery).into_bigint().to_bytes_be());
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+impl TryFrom<&ZkeyVerifyingKey> for bn::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &ZkeyVerifyingKey) -> Result<Self, Self::Error> {
+        verifying_key::<Zeropool>(vk)
+    }
+}
+
+#[cfg(feature = "ark")]
+impl TryFrom<&ZkeyVerifyingKey> for bn254::VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(vk: &ZkeyVerifyingKey) -> Result<Self, Self::Error> {
+        verifying_key::<Arkworks>(vk)
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(all(feature = "std", feature = "ark-groth16"))]
+
+mod common;
+
+#[cfg(feature = "zeropool")]
+use ark_bn254::Bn254;
+#[cfg(feature = "zeropool")]
+use ark_groth16::{prepare_verifying_key, Groth16};
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn;
+use casper_groth16::bn254;
+use common::{prove, Circuit};
+
+#[test]
+fn ark_groth16_proofs_verify_on_both_backends() {
+    for circuit in [
+        Circuit::Multiplier { a: 3, b: 11 },
+        Circuit::ManyInputs { w: 7, count: 4 },
+        Circuit::NoInputs { a: 5, b: 9 },
+    ] {
+        let (vk, proof) = prove(circuit);
+        let inputs = circuit.public_inputs();
+
+        let bn254_vk = bn254::VerifyingKey::from(&vk);
+        let bn254_proof = bn254::Proof::from(&proof);
+        let bn254_inputs = bn254::PublicInputs::from(inputs.as_slice());
+        assert_eq!(
+            bn254::verify(&bn254_vk, &bn254_proof, &bn254_inputs),
+            Ok(true),
+            "{circuit:?}"
+        );
+        // back into ark-groth16 unchanged, from either backend
+        assert_eq!(ark_groth16::VerifyingKey::from(&bn254_vk), vk);
+        assert_eq!(ark_groth16::Proof::from(&bn254_proof), proof);
+
+        #[cfg(feature = "zeropool")]
+        {
+            let bn_vk = bn::VerifyingKey::from(&vk);
+            let bn_proof = bn::Proof::from(&proof);
+            #[cfg(not(feature = "casper"))]
+            assert_eq!(
+                bn::verify(
+                    &bn_vk,
+                    &bn_proof,
+                    &bn::PublicInputs::from(inputs.as_slice())
+                ),
+                Ok(true),
+                "{circuit:?}"
+            );
+
+            let vk_back = ark_groth16::VerifyingKey::try_from(&bn_vk).unwrap();
+            let proof_back = ark_groth16::Proof::try_from(&bn_proof).unwrap();
+            assert_eq!(vk_back, vk);
+            assert_eq!(proof_back, proof);
+            let pvk = prepare_verifying_key(&vk_back);
+            assert!(Groth16::<Bn254>::verify_proof(&pvk, &proof_back, &inputs).unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+#[test]
+fn bn_coordinates_outside_base_field_are_rejected() {
+    use casper_groth16::VerifyError;
+    use casper_types::U256;
+
+    let (_, proof) = prove(Circuit::Multiplier { a: 3, b: 11 });
+    let mut bn_proof = bn::Proof::from(&proof);
+    bn_proof.c.y = U256::MAX;
+    assert_eq!(
+        ark_groth16::Proof::<Bn254>::try_from(&bn_proof),
+        Err(VerifyError::CoordinateNotInField)
+    );
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn::Zeropool;
+#[cfg(feature = "ark")]
+use casper_groth16::bn254::Arkworks;
+use casper_groth16::groth16::{Bn254Backend, G1_BYTES};
+use casper_groth16::VerifyError;
+
+fn g1_generator() -> [u8; G1_BYTES] {
+    let mut bytes = [0u8; G1_BYTES];
+    bytes[31] = 1;
+    bytes[63] = 2;
+    bytes
+}
+
+fn scalar(value: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+// 3 * G + 4 * G, encoded by the backend
+#[cfg(feature = "zeropool")]
+fn add_mul<B: Bn254Backend>() -> Result<[u8; G1_BYTES], VerifyError> {
+    let g = B::g1_from_bytes(&g1_generator())?;
+    let p = B::g1_mul(&g, &B::scalar_from_bytes(&scalar(3))?)?;
+    let q = B::g1_mul(&g, &B::scalar_from_bytes(&scalar(4))?)?;
+    Ok(B::g1_to_bytes(&B::g1_add(&p, &q)?))
+}
+
+// Verifies the snarkjs fixture through the byte encoding of `B`
+#[cfg(feature = "zeropool")]
+fn verify_fixture<B: Bn254Backend>() -> Result<bool, VerifyError> {
+    use casper_groth16::bn;
+    use casper_groth16::groth16;
+    use casper_groth16::types::{self, VerifyOptions};
+    use common::snarkjs;
+
+    let (vk, proof, inputs): (bn::VerifyingKey, bn::Proof, bn::PublicInputs) =
+        snarkjs::convert(&snarkjs::public_inputs());
+
+    let g1 = |p| B::g1_from_bytes(&Zeropool::g1_to_bytes(p));
+    let g2 = |p| B::g2_from_bytes(&Zeropool::g2_to_bytes(p));
+    let vk = types::VerifyingKey {
+        alpha1: g1(&vk.alpha1)?,
+        beta2: g2(&vk.beta2)?,
+        gamma2: g2(&vk.gamma2)?,
+        delta2: g2(&vk.delta2)?,
+        ic: vk.ic.iter().map(g1).collect::<Result<_, _>>()?,
+    };
+    let proof = types::Proof {
+        a: g1(&proof.a)?,
+        b: g2(&proof.b)?,
+        c: g1(&proof.c)?,
+    };
+    let inputs = inputs
+        .iter()
+        .map(|input| {
+            let mut bytes = [0u8; 32];
+            input.to_big_endian(&mut bytes);
+            B::scalar_from_bytes(&bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    groth16::verify::<B>(&vk, &proof, &inputs, &VerifyOptions::default())
+}
+
+#[cfg(feature = "zeropool")]
+#[test]
+fn backends_agree_on_group_operations() {
+    let zeropool = add_mul::<Zeropool>().unwrap();
+    #[cfg(feature = "ark")]
+    assert_eq!(zeropool, add_mul::<Arkworks>().unwrap());
+
+    let seven = Zeropool::g1_mul(
+        &Zeropool::g1_from_bytes(&g1_generator()).unwrap(),
+        &Zeropool::scalar_from_bytes(&scalar(7)).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Zeropool::g1_to_bytes(&seven), zeropool);
+}
+
+#[cfg(all(feature = "ark", feature = "zeropool"))]
+#[test]
+fn host_scalars_are_canonical() {
+    use casper_groth16::bn::fr_to_u256;
+    use casper_groth16::precompile::ecmul;
+
+    // `CasperHost::g1_mul` passes `fr_to_u256(k)` to `alt_bn128_mul`, which multiplies by that
+    // integer as `ECMUL` does. Products are built in the field, so no input is its own encoding.
+    let g = Zeropool::g1_from_bytes(&g1_generator()).unwrap();
+    for (a, b) in [(1, 1), (3, 11), (u64::MAX, u64::MAX)] {
+        let a = Zeropool::scalar_from_bytes(&scalar(a)).unwrap();
+        let b = Zeropool::scalar_from_bytes(&scalar(b)).unwrap();
+        let k = a * b;
+        let mut input = g1_generator().to_vec();
+        let mut word = [0u8; 32];
+        fr_to_u256(k).to_big_endian(&mut word);
+        input.extend(word);
+        assert_eq!(word, Zeropool::scalar_to_bytes(&k));
+        assert_eq!(
+            ecmul::<Arkworks>(&input),
+            Ok(Zeropool::g1_to_bytes(&Zeropool::g1_mul(&g, &k).unwrap()))
+        );
+    }
+}
+
+#[test]
+fn infinity_is_all_zero_bytes() {
+    let zero = [0u8; G1_BYTES];
+    #[cfg(feature = "ark")]
+    {
+        let p = Arkworks::g1_from_bytes(&zero).unwrap();
+        assert_eq!(Arkworks::g1_to_bytes(&p), zero);
+        let g = Arkworks::g1_from_bytes(&g1_generator()).unwrap();
+        assert_eq!(Arkworks::g1_add(&p, &g).unwrap(), g);
+    }
+
+    #[cfg(feature = "zeropool")]
+    {
+        let p = Zeropool::g1_from_bytes(&zero).unwrap();
+        let g = Zeropool::g1_from_bytes(&g1_generator()).unwrap();
+        assert_eq!(Zeropool::g1_add(&p, &g).unwrap(), g);
+    }
+}
+
+#[test]
+fn scalars_encode_canonically() {
+    for value in [0, 1, 7, u64::MAX] {
+        let bytes = scalar(value);
+        #[cfg(feature = "zeropool")]
+        let zeropool = Zeropool::scalar_from_bytes(&bytes).unwrap();
+        #[cfg(feature = "ark")]
+        let arkworks = Arkworks::scalar_from_bytes(&bytes).unwrap();
+        #[cfg(feature = "zeropool")]
+        assert_eq!(Zeropool::scalar_to_bytes(&zeropool), bytes);
+        #[cfg(feature = "ark")]
+        assert_eq!(Arkworks::scalar_to_bytes(&arkworks), bytes);
+        #[cfg(all(feature = "ark", feature = "zeropool"))]
+        assert_eq!(
+            Zeropool::scalar_to_bytes(&(zeropool * zeropool)),
+            Arkworks::scalar_to_bytes(&(arkworks * arkworks))
+        );
+    }
+}
+
+#[test]
+fn out_of_range_encodings_are_rejected() {
+    let all_ones = [0xffu8; G1_BYTES];
+    #[cfg(feature = "zeropool")]
+    {
+        assert_eq!(
+            Zeropool::g1_from_bytes(&all_ones),
+            Err(VerifyError::CoordinateNotInField)
+        );
+        assert_eq!(
+            Zeropool::scalar_from_bytes(&[0xff; 32]),
+            Err(VerifyError::ScalarOutOfRange)
+        );
+    }
+    #[cfg(feature = "ark")]
+    {
+        assert_eq!(
+            Arkworks::g1_from_bytes(&all_ones),
+            Err(VerifyError::CoordinateNotInField)
+        );
+        assert_eq!(
+            Arkworks::scalar_from_bytes(&[0xff; 32]),
+            Err(VerifyError::ScalarOutOfRange)
+        );
+    }
+}
+
+#[cfg(feature = "zeropool")]
+#[test]
+fn generic_verifier_accepts_fixture_on_every_backend() {
+    assert_eq!(verify_fixture::<Zeropool>(), Ok(true));
+    #[cfg(feature = "ark")]
+    assert_eq!(verify_fixture::<Arkworks>(), Ok(true));
+}
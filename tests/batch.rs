@@ -0,0 +1,106 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ec::CurveGroup;
+use ark_ff::Field;
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::bn;
+use casper_groth16::{bn254, VerifyError};
+use common::snarkjs;
+use num_bigint::BigUint;
+
+fn fixture() -> (bn254::VerifyingKey, bn254::Proof, bn254::PublicInputs) {
+    snarkjs::convert(&snarkjs::public_inputs())
+}
+
+// (A / s, s * B, C) satisfies the same pairing equation as (A, B, C)
+fn rerandomize(proof: &bn254::Proof, s: u64) -> bn254::Proof {
+    let s = Fr::from(s);
+    bn254::Proof {
+        a: (proof.a * s.inverse().unwrap()).into_affine(),
+        b: (proof.b * s).into_affine(),
+        c: proof.c,
+    }
+}
+
+// public_inputs proves [33, 3, 5], any other first signal is rejected
+fn batch(invalid: &[usize], len: usize) -> Vec<(bn254::Proof, bn254::PublicInputs)> {
+    let (_, proof, inputs) = fixture();
+    (0..len)
+        .map(|i| {
+            let mut inputs = inputs.clone();
+            if invalid.contains(&i) {
+                inputs.0[0] = BigUint::from(34u32);
+            }
+            (rerandomize(&proof, i as u64 + 2), inputs)
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+fn to_bn(batch: &[(bn254::Proof, bn254::PublicInputs)]) -> Vec<(bn::Proof, bn::PublicInputs)> {
+    batch
+        .iter()
+        .map(|(proof, inputs)| (common::bn_proof(proof), common::bn_inputs(inputs)))
+        .collect()
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+fn bn_vk() -> bn::VerifyingKey {
+    (&snarkjs::public_inputs().0).try_into().unwrap()
+}
+
+#[test]
+fn valid_batch_is_accepted() {
+    let (vk, _, _) = fixture();
+    let proofs = batch(&[], 4);
+    for (proof, inputs) in &proofs {
+        assert_eq!(bn254::verify(&vk, proof, inputs), Ok(true));
+    }
+    assert_eq!(bn254::verify_batch(&vk, &proofs), Ok(None));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &to_bn(&proofs)), Ok(None));
+}
+
+#[test]
+fn invalid_proof_is_located() {
+    let (vk, _, _) = fixture();
+    let proofs = batch(&[2], 4);
+    assert_eq!(bn254::verify_batch(&vk, &proofs), Ok(Some(2)));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &to_bn(&proofs)), Ok(Some(2)));
+
+    let proofs = batch(&[1, 3], 5);
+    assert_eq!(bn254::verify_batch(&vk, &proofs), Ok(Some(1)));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &to_bn(&proofs)), Ok(Some(1)));
+
+    let proofs = batch(&[0], 1);
+    assert_eq!(bn254::verify_batch(&vk, &proofs), Ok(Some(0)));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &to_bn(&proofs)), Ok(Some(0)));
+}
+
+#[test]
+fn empty_batch_is_accepted() {
+    let (vk, _, _) = fixture();
+    assert_eq!(bn254::verify_batch(&vk, &[]), Ok(None));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &[]), Ok(None));
+}
+
+#[test]
+fn malformed_entry_fails_the_batch() {
+    let (vk, _, _) = fixture();
+    let mut proofs = batch(&[], 3);
+    proofs[1].1 .0.pop();
+    let expected = Err(VerifyError::InputCountMismatch {
+        expected: 3,
+        got: 2,
+    });
+    assert_eq!(bn254::verify_batch(&vk, &proofs), expected);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(bn::verify_batch(&bn_vk(), &to_bn(&proofs)), expected);
+}
@@ -0,0 +1,137 @@
+#![cfg(all(feature = "std", feature = "zeropool"))]
+
+mod common;
+
+use casper_groth16::bn::{G1Point, Proof, PublicInputs, VerifyingKey};
+use casper_groth16::bytesrepr::{G1_SERIALIZED_LENGTH, PROOF_SERIALIZED_LENGTH};
+use casper_types::{
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    CLType, CLTyped, U256,
+};
+use common::snarkjs;
+
+fn fixture() -> (VerifyingKey, Proof, PublicInputs) {
+    snarkjs::convert(&snarkjs::public_inputs())
+}
+
+fn round_trip<T: ToBytes + FromBytes + PartialEq + std::fmt::Debug>(value: &T) -> Vec<u8> {
+    let bytes = value.to_bytes().unwrap();
+    assert_eq!(bytes.len(), value.serialized_length());
+    assert_eq!(&bytesrepr::deserialize::<T>(bytes.clone()).unwrap(), value);
+    bytes
+}
+
+#[test]
+fn proof_round_trip() {
+    let (_, proof, _) = fixture();
+    let bytes = round_trip(&proof);
+    assert_eq!(bytes.len(), PROOF_SERIALIZED_LENGTH);
+    assert_eq!(Proof::cl_type(), CLType::ByteArray(256));
+
+    let mut a_x = [0u8; 32];
+    proof.a.x.to_big_endian(&mut a_x);
+    assert_eq!(&bytes[..32], &a_x);
+    let mut b_x_c1 = [0u8; 32];
+    proof.b.x[1].to_big_endian(&mut b_x_c1);
+    assert_eq!(&bytes[96..128], &b_x_c1);
+}
+
+#[test]
+fn verifying_key_round_trip() {
+    let (vk, _, _) = fixture();
+    let bytes = round_trip(&vk);
+    // same encoding as `Bytes`: u32 length prefix followed by the raw points
+    assert_eq!(
+        u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize,
+        bytes.len() - 4
+    );
+    assert_eq!(
+        bytes.len(),
+        4 + 64 + 3 * 128 + vk.ic.len() * G1_SERIALIZED_LENGTH
+    );
+    assert_eq!(VerifyingKey::cl_type(), CLType::List(Box::new(CLType::U8)));
+}
+
+#[test]
+fn public_inputs_round_trip() {
+    let (_, _, inputs) = fixture();
+    let bytes = round_trip(&inputs);
+    assert_eq!(bytes, inputs.0.to_bytes().unwrap());
+    assert_eq!(PublicInputs::cl_type(), Vec::<U256>::cl_type());
+}
+
+#[cfg(not(feature = "casper"))]
+#[test]
+fn deserialized_proof_verifies() {
+    use casper_groth16::bn;
+
+    let (vk, proof, inputs) = fixture();
+    let vk: VerifyingKey = bytesrepr::deserialize(vk.to_bytes().unwrap()).unwrap();
+    let proof: Proof = bytesrepr::deserialize(proof.to_bytes().unwrap()).unwrap();
+    let inputs: PublicInputs = bytesrepr::deserialize(inputs.to_bytes().unwrap()).unwrap();
+    assert_eq!(bn::verify(&vk, &proof, &inputs), Ok(true));
+}
+
+#[test]
+fn deserialized_key_is_validated() {
+    use casper_groth16::bn;
+    use casper_groth16::VerifyError;
+
+    let (vk, _, _) = fixture();
+    let vk: VerifyingKey = bytesrepr::deserialize(vk.to_bytes().unwrap()).unwrap();
+    assert_eq!(bn::validate_vk(&vk), Ok(()));
+
+    let mut tampered = vk.clone();
+    tampered.ic.clear();
+    assert_eq!(
+        bn::validate_vk(&tampered),
+        Err(VerifyError::EmptyVerifyingKey)
+    );
+
+    let mut tampered = vk.clone();
+    tampered.alpha1 = G1Point::new(U256::one(), U256::from(3));
+    assert_eq!(
+        bn::validate_vk(&tampered),
+        Err(VerifyError::PointNotOnCurve)
+    );
+
+    let mut tampered = vk.clone();
+    tampered.delta2.x.swap(0, 1);
+    assert_eq!(
+        bn::validate_vk(&tampered),
+        Err(VerifyError::PointNotOnCurve)
+    );
+
+    let mut tampered = vk;
+    tampered.ic[1].y = U256::MAX;
+    assert_eq!(
+        bn::validate_vk(&tampered),
+        Err(VerifyError::CoordinateNotInField)
+    );
+}
+
+#[test]
+fn truncated_input_is_rejected() {
+    let (vk, proof, _) = fixture();
+    let bytes = proof.to_bytes().unwrap();
+    assert_eq!(
+        Proof::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        Error::EarlyEndOfStream
+    );
+
+    // an IC list that is not a whole number of points
+    let mut bytes = vk.to_bytes().unwrap();
+    bytes.truncate(bytes.len() - 1);
+    let length = (bytes.len() - 4) as u32;
+    bytes[..4].copy_from_slice(&length.to_le_bytes());
+    assert_eq!(
+        VerifyingKey::from_bytes(&bytes).unwrap_err(),
+        Error::Formatting
+    );
+
+    let point = G1Point::new(U256::one(), U256::from(2));
+    assert_eq!(
+        G1Point::from_bytes(&point.to_bytes().unwrap()[..63]).unwrap_err(),
+        Error::EarlyEndOfStream
+    );
+}
@@ -1,7 +1,18 @@
+#![cfg(all(feature = "std", any(feature = "ark", not(feature = "casper"))))]
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
 use bn::AffineG1;
-use casper_groth16::bn::{alt_bn128_pairing, compute_vk, fq_from_u256, negate_g1_affine};
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::bn::{
+    alt_bn128_pairing, compute_vk, fq_from_u256, negate_g1_affine, verify, G1Point, G2Point, Proof,
+    PublicInputs, VerifyingKey,
+};
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::snarkjs::{SnarkjsProof, SnarkjsVerifyingKey};
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
 use casper_types::U256;
 
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
 #[test]
 fn circom_multiplier_2_bn() {
     // AX
@@ -152,13 +163,13 @@ fn circom_multiplier_2_bn() {
     )
     .unwrap();
 
-    let a_neg = negate_g1_affine(pi_a_x, pi_a_y);
+    let a_neg = negate_g1_affine(pi_a_x, pi_a_y).unwrap();
     let ics = vec![
-        AffineG1::new(fq_from_u256(ic_0_x), fq_from_u256(ic_0_y)).unwrap(),
-        AffineG1::new(fq_from_u256(ic_1_x), fq_from_u256(ic_1_y)).unwrap(),
+        AffineG1::new(fq_from_u256(ic_0_x).unwrap(), fq_from_u256(ic_0_y).unwrap()).unwrap(),
+        AffineG1::new(fq_from_u256(ic_1_x).unwrap(), fq_from_u256(ic_1_y).unwrap()).unwrap(),
     ];
     let inputs = vec![U256::from(33)];
-    let vk = compute_vk(ics, inputs);
+    let vk = compute_vk(ics, inputs).unwrap();
 
     let result = alt_bn128_pairing(vec![
         (a_neg.0, a_neg.1, pi_b_x, pi_b_x2, pi_b_y, pi_b_y2),
@@ -186,10 +197,45 @@ fn circom_multiplier_2_bn() {
             vk_delta_2_y,
             vk_delta_2_y2,
         ),
-    ]);
+    ])
+    .unwrap();
     assert!(result);
 }
 
+// The same proof through the typed API, built from the snarkjs files the constants above come from
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn circom_multiplier_2_bn_typed() {
+    let vk = SnarkjsVerifyingKey::from_json(include_str!("data/multiplier2/verification_key.json"))
+        .unwrap();
+    let proof = SnarkjsProof::from_json(include_str!("data/multiplier2/proof.json")).unwrap();
+    let dec = |value: &String| U256::from_dec_str(value).unwrap();
+    let g1 = |p: &[String; 3]| G1Point::new(dec(&p[0]), dec(&p[1]));
+    let g2 = |p: &[[String; 2]; 3]| {
+        G2Point::new(
+            [dec(&p[0][0]), dec(&p[0][1])],
+            [dec(&p[1][0]), dec(&p[1][1])],
+        )
+    };
+
+    let vk = VerifyingKey {
+        alpha1: g1(&vk.vk_alpha_1),
+        beta2: g2(&vk.vk_beta_2),
+        gamma2: g2(&vk.vk_gamma_2),
+        delta2: g2(&vk.vk_delta_2),
+        ic: vk.ic.iter().map(g1).collect(),
+    };
+    let proof = Proof {
+        a: g1(&proof.pi_a),
+        b: g2(&proof.pi_b),
+        c: g1(&proof.pi_c),
+    };
+    let inputs = PublicInputs::from(vec![U256::from(33)]);
+
+    assert!(verify(&vk, &proof, &inputs).unwrap());
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
 #[test]
 fn test_pairing() {
     let ax_1 = U256::from_str_radix(
@@ -257,11 +303,13 @@ fn test_pairing() {
     let result = alt_bn128_pairing(vec![
         (ax_1, ay_1, bax_1, bay_1, bbx_1, bby_1),
         (ax_2, ay_2, bax_2, bay_2, bbx_2, bby_2),
-    ]);
+    ])
+    .unwrap();
     println!("Result: {}", &result);
     assert!(result);
 }
 
+#[cfg(feature = "ark")]
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -269,30 +317,36 @@ mod test {
     use ark_bn254::{Fq2, G1Affine, G2Affine};
     use num_bigint::BigUint;
 
-    use casper_groth16::bn254::{parse_biguint_to_fq, verify_groth16_proof, G1};
+    use casper_groth16::bn254::{
+        parse_biguint_to_fq, verify, Proof, PublicInputs, VerifyingKey, G1,
+    };
     type G2 = ark_bn254::g2::G2Affine;
 
     fn parse_biguint_to_fq2(value1: &str, value2: &str) -> Fq2 {
-        let fq1 = parse_biguint_to_fq(value1);
-        let fq2 = parse_biguint_to_fq(value2);
+        let fq1 = parse_biguint_to_fq(value1).unwrap();
+        let fq2 = parse_biguint_to_fq(value2).unwrap();
         Fq2::new(fq1, fq2)
     }
     #[test]
     fn circom_multiplier_2_bn254() {
         let pi_a_x = parse_biguint_to_fq(
             "4619434547164325081923648243067958995814461722276790408259976269673531268875",
-        );
+        )
+        .unwrap();
         let pi_a_y = parse_biguint_to_fq(
             "17285941344797724749074955491828477791926771489034344863858176130130219822865",
-        );
+        )
+        .unwrap();
         let pi_a: G1 = G1Affine::new_unchecked(pi_a_x, pi_a_y);
 
         let pi_c_x = parse_biguint_to_fq(
             "2224906812514985819002007785400739200833587017118171662746436788881490639334",
-        );
+        )
+        .unwrap();
         let pi_c_y = parse_biguint_to_fq(
             "17575872684026867761893584228054463905548398624577391451682634656255301190545",
-        );
+        )
+        .unwrap();
         let pi_c: G1 = G1Affine::new_unchecked(pi_c_x, pi_c_y);
 
         let pi_b_x = parse_biguint_to_fq2(
@@ -306,10 +360,12 @@ mod test {
         let pi_b: G2 = G2Affine::new_unchecked(pi_b_x, pi_b_y);
         let vk_alpha1_x = parse_biguint_to_fq(
             "10246350822467771900076635245792972119666566556250807950902733806864247380952",
-        );
+        )
+        .unwrap();
         let vk_alpha1_y = parse_biguint_to_fq(
             "608411288378915329930935766447369940767930506471659681097230521603283651905",
-        );
+        )
+        .unwrap();
         let vk_alpha1: G1 = G1Affine::new_unchecked(vk_alpha1_x, vk_alpha1_y);
         let vk_beta2_x = parse_biguint_to_fq2(
             "6131344741220743386799335429820992680362925873963442544072984714378368926041",
@@ -341,30 +397,37 @@ mod test {
 
         let ic_0_x = parse_biguint_to_fq(
             "21631942485326744232766849971585115612456593023934275850499378648736190910977",
-        );
+        )
+        .unwrap();
         let ic_0_y = parse_biguint_to_fq(
             "10990468352600828980319524627816836646396500759270877213016615483259184677726",
-        );
+        )
+        .unwrap();
         let ic_0: G1 = G1Affine::new_unchecked(ic_0_x, ic_0_y);
 
         let ic_1_x = parse_biguint_to_fq(
             "21229468961321243348662110358869948527418599923035918852855987234632719885365",
-        );
+        )
+        .unwrap();
         let ic_1_y = parse_biguint_to_fq(
             "14718418867019175107712538434554605791301866350066611533272126162199859274702",
-        );
+        )
+        .unwrap();
         let ic_1: G1 = G1Affine::new_unchecked(ic_1_x, ic_1_y);
-        assert!(verify_groth16_proof(
-            pi_a,
-            pi_b,
-            pi_c,
-            vk_alpha1,
-            vk_beta2,
-            vk_gamma2,
-            vk_delta2,
-            vec![ic_0, ic_1],
-            vec![BigUint::from_str("33").unwrap()]
-        ));
+        let vk = VerifyingKey {
+            alpha1: vk_alpha1,
+            beta2: vk_beta2,
+            gamma2: vk_gamma2,
+            delta2: vk_delta2,
+            ic: vec![ic_0, ic_1],
+        };
+        let proof = Proof {
+            a: pi_a,
+            b: pi_b,
+            c: pi_c,
+        };
+        let inputs = PublicInputs::from(vec![BigUint::from_str("33").unwrap()]);
+        assert!(verify(&vk, &proof, &inputs).unwrap());
     }
     #[test]
     fn circuit_with_public_inputs() {
@@ -375,18 +438,22 @@ mod test {
         ];
         let pi_a_x = parse_biguint_to_fq(
             "19392468517452974577942618696005895384800799906042106318697233463721693766857",
-        );
+        )
+        .unwrap();
         let pi_a_y = parse_biguint_to_fq(
             "11733184222349063754296049194104702852248466442201114423019855124829727281495",
-        );
+        )
+        .unwrap();
         let pi_a: G1 = G1Affine::new_unchecked(pi_a_x, pi_a_y);
 
         let pi_c_x = parse_biguint_to_fq(
             "14537178142063348772247784963013529007912999377457777806993774035571456724739",
-        );
+        )
+        .unwrap();
         let pi_c_y = parse_biguint_to_fq(
             "17288173778642609314695611486482435460623347370761147350405389833042911834390",
-        );
+        )
+        .unwrap();
         let pi_c: G1 = G1Affine::new_unchecked(pi_c_x, pi_c_y);
 
         let pi_b_x = parse_biguint_to_fq2(
@@ -400,10 +467,12 @@ mod test {
         let pi_b: G2 = G2Affine::new_unchecked(pi_b_x, pi_b_y);
         let vk_alpha1_x = parse_biguint_to_fq(
             "1492340889437497096222099246540603464242089375646843408401381497321297191805",
-        );
+        )
+        .unwrap();
         let vk_alpha1_y = parse_biguint_to_fq(
             "11206096956007645304738557692578347108012874917451451037218479742065106409283",
-        );
+        )
+        .unwrap();
         let vk_alpha1: G1 = G1Affine::new_unchecked(vk_alpha1_x, vk_alpha1_y);
         let vk_beta2_x = parse_biguint_to_fq2(
             "6819705648602020464830649412138262446645951538756802487947753732543012497761",
@@ -435,42 +504,59 @@ mod test {
 
         let ic_0_x = parse_biguint_to_fq(
             "10271593014494639556154917775587497160139512735158233514771987430693691505171",
-        );
+        )
+        .unwrap();
         let ic_0_y = parse_biguint_to_fq(
             "820244293775287856216015804235186748836699371502118506034976181750078184820",
-        );
+        )
+        .unwrap();
         let ic_0: G1 = G1Affine::new_unchecked(ic_0_x, ic_0_y);
 
         let ic_1_x = parse_biguint_to_fq(
             "2280705947019161452433451373159244292742431715288144611519626933019071363786",
-        );
+        )
+        .unwrap();
         let ic_1_y = parse_biguint_to_fq(
             "14167304281910676563969694680310119449755461008189016344190787198178442130210",
-        );
+        )
+        .unwrap();
 
         let ic_1: G1 = G1Affine::new_unchecked(ic_1_x, ic_1_y);
 
         let ic_2_x = parse_biguint_to_fq(
             "18065151204330767741864558320702649470751716898622025547025773925205377458663",
-        );
+        )
+        .unwrap();
         let ic_2_y = parse_biguint_to_fq(
             "12530120613599435509444558723909129574908256194829780222525439733802640757968",
-        );
+        )
+        .unwrap();
 
         let ic_2: G1 = G1Affine::new_unchecked(ic_2_x, ic_2_y);
 
         let ic_3_x = parse_biguint_to_fq(
             "2515573466743927184129285920552961694034693235978720556942741443996060153714",
-        );
+        )
+        .unwrap();
         let ic_3_y = parse_biguint_to_fq(
             "10527719347406676325186974791933879637257851126926242922361792698025261451931",
-        );
+        )
+        .unwrap();
 
         let ic_3: G1 = G1Affine::new_unchecked(ic_3_x, ic_3_y);
 
-        let ics = vec![ic_0, ic_1, ic_2, ic_3];
-        assert!(verify_groth16_proof(
-            pi_a, pi_b, pi_c, vk_alpha1, vk_beta2, vk_gamma2, vk_delta2, ics, inputs
-        ));
+        let vk = VerifyingKey {
+            alpha1: vk_alpha1,
+            beta2: vk_beta2,
+            gamma2: vk_gamma2,
+            delta2: vk_delta2,
+            ic: vec![ic_0, ic_1, ic_2, ic_3],
+        };
+        let proof = Proof {
+            a: pi_a,
+            b: pi_b,
+            c: pi_c,
+        };
+        assert!(verify(&vk, &proof, &PublicInputs::from(inputs)).unwrap());
     }
 }
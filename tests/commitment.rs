@@ -0,0 +1,159 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+mod common;
+
+use ark_bn254::{Fr, G1Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn::Zeropool;
+use casper_groth16::bn254::{self, Arkworks};
+use casper_groth16::commitment::{expand_message_xmd, hash_to_scalar, COMMITMENT_DST};
+#[cfg(feature = "zeropool")]
+use casper_groth16::groth16::Bn254Backend;
+use casper_groth16::VerifyError;
+use common::CommittedFixture;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// The result of both backends, which have to agree
+fn verify(fixture: &CommittedFixture) -> Result<bool, VerifyError> {
+    let expected = bn254::verify_committed(&fixture.vk, &fixture.proof, &fixture.inputs);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(
+        casper_groth16::bn::verify_committed(
+            &fixture.bn_vk(),
+            &fixture.bn_proof(),
+            &common::bn_inputs(&fixture.inputs),
+        ),
+        expected
+    );
+    expected
+}
+
+#[test]
+fn expand_message_xmd_matches_rfc_9380() {
+    // RFC 9380, appendix K.1
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    assert_eq!(
+        hex(&expand_message_xmd(b"", dst, 0x20).unwrap()),
+        "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+    );
+    assert_eq!(
+        hex(&expand_message_xmd(b"abc", dst, 0x20).unwrap()),
+        "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+    );
+    assert_eq!(
+        hex(&expand_message_xmd(b"", dst, 0x80).unwrap()),
+        "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+         e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+         eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+         c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+    );
+}
+
+#[test]
+fn expand_message_xmd_rejects_lengths_outside_rfc_9380() {
+    let invalid = Err(VerifyError::InvalidEncoding);
+    assert_eq!(
+        expand_message_xmd(b"", COMMITMENT_DST, 255 * 32 + 1),
+        invalid
+    );
+    assert_eq!(expand_message_xmd(b"", &[0; 256], 32), invalid);
+    assert!(expand_message_xmd(b"", &[0; 255], 255 * 32).is_ok());
+    assert_eq!(
+        hash_to_scalar::<Arkworks>(b"", &[0; 256]),
+        Err(VerifyError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn hash_to_scalar_reduces_48_bytes() {
+    for msg in [&b""[..], b"abc", &[0xff; 100]] {
+        let expected =
+            Fr::from_be_bytes_mod_order(&expand_message_xmd(msg, COMMITMENT_DST, 48).unwrap());
+        assert_eq!(
+            hash_to_scalar::<Arkworks>(msg, COMMITMENT_DST),
+            Ok(expected)
+        );
+        #[cfg(feature = "zeropool")]
+        assert_eq!(
+            Zeropool::scalar_to_bytes(&hash_to_scalar::<Zeropool>(msg, COMMITMENT_DST).unwrap()),
+            Arkworks::scalar_to_bytes(&expected)
+        );
+    }
+}
+
+#[test]
+fn committed_proofs_verify_on_both_backends() {
+    for (seed, inputs, committed) in [
+        (1, 2, vec![vec![1]]),
+        (2, 3, vec![vec![1, 3], vec![]]),
+        // the second commitment commits to the input derived from the first
+        (3, 1, vec![vec![1], vec![2], vec![1, 3]]),
+        (4, 0, vec![vec![]]),
+        (5, 2, vec![]),
+    ] {
+        let fixture = CommittedFixture::new(seed, inputs, committed);
+        assert_eq!(verify(&fixture), Ok(true), "seed {seed}");
+    }
+}
+
+#[test]
+fn tampered_commitments_are_rejected() {
+    let fixture = CommittedFixture::new(6, 2, vec![vec![1, 2], vec![2]]);
+
+    let mut pok = CommittedFixture::new(6, 2, vec![vec![1, 2], vec![2]]);
+    pok.proof.commitment_pok = (pok.proof.commitment_pok + G1Affine::generator()).into_affine();
+    assert_eq!(verify(&pok), Ok(false));
+
+    // moves the derived inputs as well, the pairing check and the proof of knowledge both fail
+    let mut commitment = CommittedFixture::new(6, 2, vec![vec![1, 2], vec![2]]);
+    commitment.proof.commitments[1] =
+        (commitment.proof.commitments[1] + G1Affine::generator()).into_affine();
+    assert_eq!(verify(&commitment), Ok(false));
+
+    let mut inputs = CommittedFixture::new(6, 2, vec![vec![1, 2], vec![2]]);
+    inputs.inputs.0[1] += 1u8;
+    assert_eq!(verify(&inputs), Ok(false));
+
+    // swapping the committed inputs changes the derived inputs
+    let mut committed = CommittedFixture::new(6, 2, vec![vec![1, 2], vec![2]]);
+    committed.vk.public_committed[0] = vec![2, 1];
+    assert_eq!(verify(&committed), Ok(false));
+
+    assert_eq!(verify(&fixture), Ok(true));
+}
+
+#[test]
+fn malformed_commitments_are_rejected() {
+    let mut missing = CommittedFixture::new(7, 1, vec![vec![1], vec![]]);
+    missing.proof.commitments.pop();
+    let mismatch = Err(VerifyError::CommitmentCountMismatch {
+        expected: 2,
+        got: 1,
+    });
+    assert_eq!(verify(&missing), mismatch);
+
+    let mut index = CommittedFixture::new(7, 1, vec![vec![1], vec![]]);
+    index.vk.public_committed[0] = vec![3];
+    let invalid = Err(VerifyError::InvalidEncoding);
+    assert_eq!(verify(&index), invalid);
+    index.vk.public_committed[0] = vec![0];
+    assert_eq!(verify(&index), invalid);
+
+    let mut count = CommittedFixture::new(7, 1, vec![vec![1], vec![]]);
+    count.inputs.0.push(1u8.into());
+    let inputs = Err(VerifyError::InputCountMismatch {
+        expected: 1,
+        got: 2,
+    });
+    assert_eq!(verify(&count), inputs);
+
+    let mut keys = CommittedFixture::new(7, 1, vec![vec![1], vec![]]);
+    keys.vk.commitment_keys[1].g = (keys.vk.commitment_keys[1].g * Fr::from(2u8)).into_affine();
+    let unsupported = Err(VerifyError::Unsupported);
+    assert_eq!(verify(&keys), unsupported);
+}
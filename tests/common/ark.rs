@@ -0,0 +1,351 @@
+// Groth16 fixtures generated during the test run: a trusted setup and prover from ark-groth16 over
+// a few small R1CS circuits, and proofs with gnark's Pedersen commitments simulated from a known
+// trapdoor, in `bn254` types. The conversions to `bn` types also need `zeropool`.
+
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_groth16::{Groth16, Proof, VerifyingKey};
+use ark_relations::lc;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn::{self, Zeropool};
+use casper_groth16::bn254;
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn254::Arkworks;
+#[cfg(feature = "zeropool")]
+use casper_groth16::groth16::Bn254Backend;
+use casper_groth16::types;
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+/// The circuits a fixture can be generated for.
+#[derive(Clone, Copy, Debug)]
+pub enum Circuit {
+    /// Knows `a` and `b` with `a * b == c`, public `[c]`.
+    Multiplier { a: u64, b: u64 },
+    /// Knows the bits of `value`, i.e. `value < 2^bits`, public `[value]`.
+    RangeCheck { value: u64, bits: u32 },
+    /// Knows `w` with `inputs[i] == (i + 1) * w`, `count` public inputs.
+    ManyInputs { w: u64, count: usize },
+    /// Knows `a`, `b` and `c` with `a * b == c`, no public inputs.
+    NoInputs { a: u64, b: u64 },
+}
+
+impl Circuit {
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        match *self {
+            Circuit::Multiplier { a, b } => vec![Fr::from(a) * Fr::from(b)],
+            Circuit::RangeCheck { value, .. } => vec![Fr::from(value)],
+            Circuit::ManyInputs { w, count } => (1..=count as u64)
+                .map(|i| Fr::from(i) * Fr::from(w))
+                .collect(),
+            Circuit::NoInputs { .. } => vec![],
+        }
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for Circuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let inputs = self
+            .public_inputs()
+            .into_iter()
+            .map(|input| cs.new_input_variable(|| Ok(input)))
+            .collect::<Result<Vec<_>, _>>()?;
+        match self {
+            Circuit::Multiplier { a, b } => {
+                let a = cs.new_witness_variable(|| Ok(Fr::from(a)))?;
+                let b = cs.new_witness_variable(|| Ok(Fr::from(b)))?;
+                cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + inputs[0])
+            }
+            Circuit::RangeCheck { value, bits } => {
+                let mut sum = lc!();
+                for i in 0..bits {
+                    let bit = cs.new_witness_variable(|| Ok(Fr::from((value >> i) & 1)))?;
+                    cs.enforce_constraint(lc!() + bit, lc!() + bit, lc!() + bit)?;
+                    sum += (Fr::from(2u8).pow([u64::from(i)]), bit);
+                }
+                cs.enforce_constraint(sum, lc!() + Variable::One, lc!() + inputs[0])
+            }
+            Circuit::ManyInputs { w, .. } => {
+                let w = cs.new_witness_variable(|| Ok(Fr::from(w)))?;
+                for (i, input) in inputs.into_iter().enumerate() {
+                    let factor = Fr::from(i as u64 + 1);
+                    cs.enforce_constraint(
+                        lc!() + w,
+                        lc!() + (factor, Variable::One),
+                        lc!() + input,
+                    )?;
+                }
+                Ok(())
+            }
+            Circuit::NoInputs { a, b } => {
+                let c = cs.new_witness_variable(|| Ok(Fr::from(a) * Fr::from(b)))?;
+                let a = cs.new_witness_variable(|| Ok(Fr::from(a)))?;
+                let b = cs.new_witness_variable(|| Ok(Fr::from(b)))?;
+                cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + c)
+            }
+        }
+    }
+}
+
+/// Runs the setup and prover with a fixed seed, so every run produces the same key and proof. The
+/// prover asserts that the circuit is satisfied in debug builds.
+pub fn prove(circuit: Circuit) -> (VerifyingKey<Bn254>, Proof<Bn254>) {
+    let mut rng = StdRng::seed_from_u64(0x6772_6f74_6831_3600);
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit, &mut rng)
+        .expect("fixture circuits synthesize");
+    let proof = Groth16::<Bn254>::create_random_proof_with_reduction(circuit, &pk, &mut rng)
+        .expect("fixture circuits synthesize");
+    (pk.vk, proof)
+}
+
+/// A fresh setup for a circuit and a proof with its public inputs, in `bn254` types.
+pub struct Fixture {
+    pub vk: bn254::VerifyingKey,
+    pub proof: bn254::Proof,
+    pub inputs: bn254::PublicInputs,
+}
+
+impl Fixture {
+    pub fn new(circuit: Circuit) -> Self {
+        let (vk, proof) = prove(circuit);
+        // by hand, the conversions in `casper_groth16::ark_groth16` need the `ark-groth16` feature
+        Fixture {
+            vk: types::VerifyingKey {
+                alpha1: vk.alpha_g1,
+                beta2: vk.beta_g2,
+                gamma2: vk.gamma_g2,
+                delta2: vk.delta_g2,
+                ic: vk.gamma_abc_g1,
+            },
+            proof: types::Proof {
+                a: proof.a,
+                b: proof.b,
+                c: proof.c,
+            },
+            inputs: types::PublicInputs(
+                circuit
+                    .public_inputs()
+                    .iter()
+                    .map(|input| BigUint::from(input.into_bigint()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The proof with `C` moved by the generator, still a valid curve point.
+    pub fn tampered_proof(&self) -> bn254::Proof {
+        types::Proof {
+            c: (self.proof.c + G1Affine::generator()).into_affine(),
+            ..self.proof
+        }
+    }
+
+    /// The public inputs with `inputs[i]` incremented.
+    pub fn tampered_inputs(&self, i: usize) -> bn254::PublicInputs {
+        let mut inputs = self.inputs.clone();
+        inputs.0[i] += 1u8;
+        inputs
+    }
+}
+
+fn be_bytes(value: impl PrimeField) -> Vec<u8> {
+    let bytes = value.into_bigint().to_bytes_be();
+    [vec![0; 32 - bytes.len()], bytes].concat()
+}
+
+/// gnark's `fr.Hash`: 48 bytes of RFC 9380 `expand_message_xmd` with SHA-256, reduced modulo `r`.
+/// Written out here rather than taken from `casper_groth16::commitment`, so the fixtures check its
+/// hash and domain separation tags instead of sharing them.
+fn gnark_hash(msg: &[u8], dst: &[u8]) -> Fr {
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let b0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update(48u16.to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let b1 = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let xor: Vec<u8> = b0.iter().zip(&b1).map(|(x, y)| x ^ y).collect();
+    let b2 = Sha256::new()
+        .chain_update(xor)
+        .chain_update([2u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    Fr::from_be_bytes_mod_order(&[&b1[..], &b2[..16]].concat())
+}
+
+/// Simulates a key with IC points `ic[i] G1` and a proof for `inputs` from the trapdoor
+/// `[alpha, beta, gamma, delta, a, b]`: with `A = a G1`, `B = b G2` and `vk_x = v G1`,
+/// `C = (a b - alpha beta - v gamma) / delta G1` satisfies the pairing check. `extra` is what `vk_x`
+/// holds besides the IC terms, the commitments of a committed proof. A zero `ic[i]` is an IC point
+/// at infinity, and `a == 0` puts `A` at infinity while the pairing equation still holds.
+pub fn simulate(
+    trapdoor: [Fr; 6],
+    ic: &[Fr],
+    inputs: &[Fr],
+    extra: Fr,
+) -> (bn254::VerifyingKey, bn254::Proof) {
+    let [alpha, beta, gamma, delta, a, b] = trapdoor;
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let v = ic[0] + ic[1..].iter().zip(inputs).map(|(u, x)| *u * x).sum::<Fr>() + extra;
+    let c = (a * b - alpha * beta - v * gamma) * delta.inverse().unwrap();
+    (
+        types::VerifyingKey {
+            alpha1: (g1 * alpha).into_affine(),
+            beta2: (g2 * beta).into_affine(),
+            gamma2: (g2 * gamma).into_affine(),
+            delta2: (g2 * delta).into_affine(),
+            ic: ic.iter().map(|u| (g1 * u).into_affine()).collect(),
+        },
+        types::Proof {
+            a: (g1 * a).into_affine(),
+            b: (g2 * b).into_affine(),
+            c: (g1 * c).into_affine(),
+        },
+    )
+}
+
+/// A key, proof and inputs with gnark's Pedersen commitments, in `bn254` types.
+pub struct CommittedFixture {
+    pub vk: bn254::CommittedVerifyingKey,
+    pub proof: bn254::CommittedProof,
+    pub inputs: bn254::PublicInputs,
+}
+
+impl CommittedFixture {
+    /// Simulates a proof for `inputs` random public inputs and one commitment per entry of
+    /// `public_committed`, from the trapdoor of a random key: every commitment key shares `g` and
+    /// has its own `sigma`, the proof of knowledge is `sum(r^j sigma_j C_j)` and, with
+    /// `vk_x = v G1`, `C = (a b - alpha beta - v gamma) / delta G1`.
+    pub fn new(seed: u64, inputs: usize, public_committed: Vec<Vec<usize>>) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let [alpha, beta, gamma, delta, a, b, tau] = [(); 7].map(|_| Fr::rand(&mut rng));
+        let trapdoor = [alpha, beta, gamma, delta, a, b];
+        let n = public_committed.len();
+        let ic: Vec<Fr> = (0..=inputs + n).map(|_| Fr::rand(&mut rng)).collect();
+        let mut values: Vec<Fr> = (0..inputs).map(|_| Fr::rand(&mut rng)).collect();
+        let committed: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+        let sigmas: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+
+        let commitments: Vec<G1Affine> = committed.iter().map(|c| (g1 * c).into_affine()).collect();
+        for (indices, commitment) in public_committed.iter().zip(&commitments) {
+            let mut msg = [be_bytes(commitment.x), be_bytes(commitment.y)].concat();
+            for index in indices {
+                msg.extend(be_bytes(values[index - 1]));
+            }
+            values.push(gnark_hash(&msg, b"bsb22-commitment"));
+        }
+        let transcript: Vec<u8> = values[inputs..].iter().flat_map(|v| be_bytes(*v)).collect();
+        let r = gnark_hash(&transcript, b"G16-BSB22");
+        let pok = (0..n)
+            .map(|j| r.pow([j as u64]) * sigmas[j] * committed[j])
+            .sum::<Fr>();
+
+        let (vk, proof) = simulate(trapdoor, &ic, &values, committed.iter().sum());
+        let g = g2 * tau;
+        CommittedFixture {
+            vk: types::CommittedVerifyingKey {
+                vk,
+                public_committed,
+                commitment_keys: sigmas
+                    .iter()
+                    .map(|sigma| types::CommitmentKey {
+                        g: g.into_affine(),
+                        g_sigma_neg: (g * -*sigma).into_affine(),
+                    })
+                    .collect(),
+            },
+            proof: types::CommittedProof {
+                proof,
+                commitments,
+                commitment_pok: (g1 * pok).into_affine(),
+            },
+            inputs: types::PublicInputs(
+                values[..inputs]
+                    .iter()
+                    .map(|input| BigUint::from(input.into_bigint()))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[cfg(feature = "zeropool")]
+    pub fn bn_vk(&self) -> bn::CommittedVerifyingKey {
+        types::CommittedVerifyingKey {
+            vk: bn_vk(&self.vk.vk),
+            public_committed: self.vk.public_committed.clone(),
+            commitment_keys: self
+                .vk
+                .commitment_keys
+                .iter()
+                .map(|key| types::CommitmentKey {
+                    g: bn_g2(&key.g),
+                    g_sigma_neg: bn_g2(&key.g_sigma_neg),
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "zeropool")]
+    pub fn bn_proof(&self) -> bn::CommittedProof {
+        types::CommittedProof {
+            proof: bn_proof(&self.proof.proof),
+            commitments: self.proof.commitments.iter().map(bn_g1).collect(),
+            commitment_pok: bn_g1(&self.proof.commitment_pok),
+        }
+    }
+}
+
+#[cfg(feature = "zeropool")]
+pub fn bn_vk(vk: &bn254::VerifyingKey) -> bn::VerifyingKey {
+    types::VerifyingKey {
+        alpha1: bn_g1(&vk.alpha1),
+        beta2: bn_g2(&vk.beta2),
+        gamma2: bn_g2(&vk.gamma2),
+        delta2: bn_g2(&vk.delta2),
+        ic: vk.ic.iter().map(bn_g1).collect(),
+    }
+}
+
+#[cfg(feature = "zeropool")]
+pub fn bn_proof(proof: &bn254::Proof) -> bn::Proof {
+    types::Proof {
+        a: bn_g1(&proof.a),
+        b: bn_g2(&proof.b),
+        c: bn_g1(&proof.c),
+    }
+}
+
+#[cfg(feature = "zeropool")]
+pub fn bn_inputs(inputs: &bn254::PublicInputs) -> bn::PublicInputs {
+    types::PublicInputs(
+        inputs
+            .iter()
+            .map(|input| U256::from_big_endian(&input.to_bytes_be()))
+            .collect(),
+    )
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_g1(p: &G1Affine) -> bn::G1Point {
+    Zeropool::g1_from_bytes(&Arkworks::g1_to_bytes(p)).expect("arkworks points are in range")
+}
+
+#[cfg(feature = "zeropool")]
+fn bn_g2(p: &ark_bn254::G2Affine) -> bn::G2Point {
+    Zeropool::g2_from_bytes(&Arkworks::g2_to_bytes(p)).expect("arkworks points are in range")
+}
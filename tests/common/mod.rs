@@ -0,0 +1,11 @@
+// Helpers shared by the integration tests, include with `mod common;`. `snarkjs` loads the snarkjs
+// fixtures in tests/data, the fixtures generated with arkworks need `ark`.
+#![allow(dead_code)]
+
+#[cfg(feature = "ark")]
+mod ark;
+pub mod snarkjs;
+
+#[cfg(feature = "ark")]
+#[allow(unused_imports)]
+pub use ark::*;
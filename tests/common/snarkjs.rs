@@ -0,0 +1,39 @@
+// The snarkjs fixtures in tests/data, parsed and converted with the library's own loaders.
+use casper_groth16::snarkjs::{SnarkjsProof, SnarkjsPublicSignals, SnarkjsVerifyingKey};
+use casper_groth16::VerifyError;
+
+pub type Snarkjs = (SnarkjsVerifyingKey, SnarkjsProof, SnarkjsPublicSignals);
+
+/// `multiplier2`, a proof of `a * b == c` with public `[33]`.
+pub fn multiplier2() -> Snarkjs {
+    (
+        SnarkjsVerifyingKey::from_json(include_str!("../data/multiplier2/verification_key.json"))
+            .unwrap(),
+        SnarkjsProof::from_json(include_str!("../data/multiplier2/proof.json")).unwrap(),
+        SnarkjsPublicSignals::from_json(include_str!("../data/multiplier2/public.json")).unwrap(),
+    )
+}
+
+/// `public_inputs`, a proof with public `[33, 3, 5]`.
+pub fn public_inputs() -> Snarkjs {
+    (
+        SnarkjsVerifyingKey::from_json(include_str!("../data/public_inputs/verification_key.json"))
+            .unwrap(),
+        SnarkjsProof::from_json(include_str!("../data/public_inputs/proof.json")).unwrap(),
+        SnarkjsPublicSignals::from_json(include_str!("../data/public_inputs/public.json")).unwrap(),
+    )
+}
+
+/// Converts a fixture into `bn` or `bn254` types through the `TryFrom` impls of `snarkjs`.
+pub fn convert<K, P, I>((vk, proof, public): &Snarkjs) -> (K, P, I)
+where
+    K: for<'a> TryFrom<&'a SnarkjsVerifyingKey, Error = VerifyError>,
+    P: for<'a> TryFrom<&'a SnarkjsProof, Error = VerifyError>,
+    I: for<'a> TryFrom<&'a SnarkjsPublicSignals, Error = VerifyError>,
+{
+    (
+        K::try_from(vk).unwrap(),
+        P::try_from(proof).unwrap(),
+        I::try_from(public).unwrap(),
+    )
+}
@@ -0,0 +1,318 @@
+#![cfg(all(feature = "std", feature = "ark", feature = "zeropool"))]
+
+// Runs the same random inputs through `bn::Zeropool` and `bn254::Arkworks` and fails on the first
+// result that differs, printing the seed, the case and every input in the backend byte encoding.
+// Set DIFFERENTIAL_SEED to replay or vary a run.
+
+mod common;
+
+use std::fmt::Debug;
+
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, Zero};
+use casper_groth16::bn::Zeropool;
+use casper_groth16::bn254::Arkworks;
+use casper_groth16::groth16::{self, Bn254Backend, G1_BYTES, G2_BYTES, SCALAR_BYTES};
+use casper_groth16::types::{Proof, VerifyOptions, VerifyingKey};
+use casper_groth16::VerifyError;
+use sha2::{Digest, Sha256};
+
+const BASE_FIELD_MODULUS: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+const SCALAR_FIELD_MODULUS: &str =
+    "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn word(value: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+    }
+    bytes
+}
+
+/// SHA-256 in counter mode, deterministic for a given seed.
+struct Rng {
+    seed: u64,
+    counter: u64,
+}
+
+impl Rng {
+    fn new() -> Self {
+        let seed = std::env::var("DIFFERENTIAL_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(0x0067_726f_7468_3136);
+        Rng { seed, counter: 0 }
+    }
+
+    fn bytes(&mut self) -> [u8; 32] {
+        self.counter += 1;
+        let mut hasher = Sha256::new();
+        hasher.update(self.seed.to_be_bytes());
+        hasher.update(self.counter.to_be_bytes());
+        hasher.finalize().into()
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        u64::from_be_bytes(self.bytes()[..8].try_into().unwrap()) % n
+    }
+
+    fn fr(&mut self) -> Fr {
+        Fr::from_be_bytes_mod_order(&self.bytes())
+    }
+
+    /// Mostly uniform scalars, with the edges of the range and a few out of range values.
+    fn scalar(&mut self) -> [u8; SCALAR_BYTES] {
+        let r = word(SCALAR_FIELD_MODULUS);
+        match self.below(16) {
+            0 => [0u8; SCALAR_BYTES],
+            1 => Arkworks::scalar_to_bytes(&Fr::from(1u8)),
+            2 => Arkworks::scalar_to_bytes(&-Fr::from(1u8)),
+            3 => r,
+            4 => [0xff; SCALAR_BYTES],
+            _ => Arkworks::scalar_to_bytes(&self.fr()),
+        }
+    }
+
+    /// Mostly random multiples of the generator, with infinity, off-curve points and coordinates
+    /// outside the base field mixed in.
+    fn g1(&mut self) -> [u8; G1_BYTES] {
+        let mut bytes = [0u8; G1_BYTES];
+        match self.below(16) {
+            0 => {}
+            1 => bytes = Arkworks::g1_to_bytes(&G1Affine::generator()),
+            2 => {
+                bytes[..32].copy_from_slice(&Arkworks::scalar_to_bytes(&self.fr()));
+                bytes[32..].copy_from_slice(&Arkworks::scalar_to_bytes(&self.fr()));
+            }
+            3 => {
+                bytes = Arkworks::g1_to_bytes(&G1Affine::generator());
+                bytes[..32].copy_from_slice(&word(BASE_FIELD_MODULUS));
+            }
+            4 => bytes[63] = 2,
+            _ => bytes = Arkworks::g1_to_bytes(&(G1Affine::generator() * self.fr()).into_affine()),
+        }
+        bytes
+    }
+}
+
+fn g1_point<B: Bn254Backend>(bytes: &[u8; G1_BYTES]) -> Result<B::G1, VerifyError> {
+    let p = B::g1_from_bytes(bytes)?;
+    B::validate_g1(&p)?;
+    Ok(p)
+}
+
+type G1Bytes = Result<[u8; G1_BYTES], VerifyError>;
+
+fn add<B: Bn254Backend>(p: &[u8; G1_BYTES], q: &[u8; G1_BYTES]) -> G1Bytes {
+    let sum = B::g1_add(&g1_point::<B>(p)?, &g1_point::<B>(q)?)?;
+    Ok(B::g1_to_bytes(&sum))
+}
+
+fn mul<B: Bn254Backend>(p: &[u8; G1_BYTES], k: &[u8; SCALAR_BYTES]) -> G1Bytes {
+    let product = B::g1_mul(&g1_point::<B>(p)?, &B::scalar_from_bytes(k)?)?;
+    Ok(B::g1_to_bytes(&product))
+}
+
+fn neg<B: Bn254Backend>(p: &[u8; G1_BYTES]) -> G1Bytes {
+    Ok(B::g1_to_bytes(&B::g1_neg(&g1_point::<B>(p)?)?))
+}
+
+fn vk_x<B: Bn254Backend>(ic: &[[u8; G1_BYTES]], inputs: &[[u8; SCALAR_BYTES]]) -> G1Bytes {
+    let ic = ic
+        .iter()
+        .map(g1_point::<B>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let inputs = inputs
+        .iter()
+        .map(B::scalar_from_bytes)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(B::g1_to_bytes(&groth16::compute_vk_x::<B>(&ic, &inputs)?))
+}
+
+/// A verifying key, proof and inputs in the `Bn254Backend` byte encoding.
+struct ProofCase {
+    g1: Vec<[u8; G1_BYTES]>,
+    g2: Vec<[u8; G2_BYTES]>,
+    inputs: Vec<[u8; SCALAR_BYTES]>,
+}
+
+impl ProofCase {
+    // g1 is alpha1, A, C, ic..., g2 is beta2, gamma2, delta2, B
+    fn new(
+        vk: &VerifyingKey<G1Affine, G2Affine>,
+        proof: &Proof<G1Affine, G2Affine>,
+        inputs: &[Fr],
+    ) -> Self {
+        let mut g1 = vec![vk.alpha1, proof.a, proof.c];
+        g1.extend(&vk.ic);
+        ProofCase {
+            g1: g1.iter().map(Arkworks::g1_to_bytes).collect(),
+            g2: [vk.beta2, vk.gamma2, vk.delta2, proof.b]
+                .iter()
+                .map(Arkworks::g2_to_bytes)
+                .collect(),
+            inputs: inputs.iter().map(Arkworks::scalar_to_bytes).collect(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        let g1: Vec<_> = self.g1.iter().map(|p| hex(p)).collect();
+        let g2: Vec<_> = self.g2.iter().map(|p| hex(p)).collect();
+        let inputs: Vec<_> = self.inputs.iter().map(|k| hex(k)).collect();
+        format!(
+            "g1 (alpha1, A, C, ic) {g1:?}\ng2 (beta2, gamma2, delta2, B) {g2:?}\ninputs {inputs:?}"
+        )
+    }
+
+    fn verify<B: Bn254Backend>(&self) -> Result<bool, VerifyError> {
+        let g1 = self
+            .g1
+            .iter()
+            .map(B::g1_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2 = self
+            .g2
+            .iter()
+            .map(B::g2_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let vk = VerifyingKey {
+            alpha1: g1[0].clone(),
+            beta2: g2[0].clone(),
+            gamma2: g2[1].clone(),
+            delta2: g2[2].clone(),
+            ic: g1[3..].to_vec(),
+        };
+        let proof = Proof {
+            a: g1[1].clone(),
+            b: g2[3].clone(),
+            c: g1[2].clone(),
+        };
+        let inputs = self
+            .inputs
+            .iter()
+            .map(B::scalar_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        groth16::verify::<B>(&vk, &proof, &inputs, &VerifyOptions::default())
+    }
+}
+
+/// `common::simulate` under a random trapdoor and IC, as a `ProofCase`.
+fn simulate(rng: &mut Rng, inputs: &[Fr]) -> ProofCase {
+    let trapdoor = [(); 6].map(|_| rng.fr());
+    let ic: Vec<Fr> = (0..=inputs.len()).map(|_| rng.fr()).collect();
+    let (vk, proof) = common::simulate(trapdoor, &ic, inputs, Fr::zero());
+    ProofCase::new(&vk, &proof, inputs)
+}
+
+fn agree<T: PartialEq + Debug>(
+    rng: &Rng,
+    case: usize,
+    op: &str,
+    inputs: &str,
+    zeropool: Result<T, VerifyError>,
+    arkworks: Result<T, VerifyError>,
+) {
+    assert!(
+        zeropool == arkworks,
+        "backends diverge on {op} (DIFFERENTIAL_SEED={}, case {case})\n{inputs}\nzeropool: {zeropool:?}\narkworks: {arkworks:?}",
+        rng.seed
+    );
+}
+
+#[test]
+fn backends_agree_on_group_operations() {
+    let mut rng = Rng::new();
+    for case in 0..256 {
+        let (p, q, k) = (rng.g1(), rng.g1(), rng.scalar());
+        // P + P and P + (-P) take different branches of the addition formulas
+        let q = match rng.below(4) {
+            0 => p,
+            1 => neg::<Arkworks>(&p).unwrap_or(q),
+            _ => q,
+        };
+        let inputs = format!("p {}\nq {}\nk {}", hex(&p), hex(&q), hex(&k));
+        let zeropool = add::<Zeropool>(&p, &q).map(|sum| hex(&sum));
+        let arkworks = add::<Arkworks>(&p, &q).map(|sum| hex(&sum));
+        agree(&rng, case, "g1_add", &inputs, zeropool, arkworks);
+        let zeropool = mul::<Zeropool>(&p, &k).map(|product| hex(&product));
+        let arkworks = mul::<Arkworks>(&p, &k).map(|product| hex(&product));
+        agree(&rng, case, "g1_mul", &inputs, zeropool, arkworks);
+        let zeropool = neg::<Zeropool>(&p).map(|neg| hex(&neg));
+        let arkworks = neg::<Arkworks>(&p).map(|neg| hex(&neg));
+        agree(&rng, case, "g1_neg", &inputs, zeropool, arkworks);
+    }
+}
+
+#[test]
+fn backends_agree_on_vk_x() {
+    let mut rng = Rng::new();
+    for case in 0..64 {
+        let ic: Vec<_> = (0..rng.below(6)).map(|_| rng.g1()).collect();
+        // mostly the right number of inputs
+        let len = match rng.below(8) {
+            0 => rng.below(6) as usize,
+            _ => ic.len().saturating_sub(1),
+        };
+        let inputs: Vec<_> = (0..len).map(|_| rng.scalar()).collect();
+        let description = format!(
+            "ic {:?}\ninputs {:?}",
+            ic.iter().map(|p| hex(p)).collect::<Vec<_>>(),
+            inputs.iter().map(|k| hex(k)).collect::<Vec<_>>()
+        );
+        agree(
+            &rng,
+            case,
+            "compute_vk_x",
+            &description,
+            vk_x::<Zeropool>(&ic, &inputs).map(|p| hex(&p)),
+            vk_x::<Arkworks>(&ic, &inputs).map(|p| hex(&p)),
+        );
+    }
+}
+
+#[test]
+fn backends_agree_on_simulated_proofs() {
+    let mut rng = Rng::new();
+    for case in 0..8 {
+        let inputs: Vec<Fr> = (0..rng.below(4)).map(|_| rng.fr()).collect();
+        let mut proof = simulate(&mut rng, &inputs);
+        let description = proof.describe();
+        let zeropool = proof.verify::<Zeropool>();
+        agree(
+            &rng,
+            case,
+            "verify",
+            &description,
+            zeropool,
+            proof.verify::<Arkworks>(),
+        );
+        assert_eq!(
+            zeropool,
+            Ok(true),
+            "simulated proof rejected\n{description}"
+        );
+
+        // tamper with C, an input or A, every change must be rejected by both
+        match rng.below(3) {
+            0 => proof.g1[2] = rng.g1(),
+            1 if !proof.inputs.is_empty() => proof.inputs[0] = rng.scalar(),
+            _ => proof.g1[1] = rng.g1(),
+        }
+        let description = proof.describe();
+        let zeropool = proof.verify::<Zeropool>();
+        agree(
+            &rng,
+            case,
+            "verify",
+            &description,
+            zeropool,
+            proof.verify::<Arkworks>(),
+        );
+        assert_ne!(zeropool, Ok(true), "tampered proof accepted\n{description}");
+    }
+}
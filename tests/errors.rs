@@ -0,0 +1,84 @@
+#![cfg(any(feature = "ark", not(feature = "casper")))]
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::bn::{alt_bn128_add, alt_bn128_mul, alt_bn128_pairing, compute_vk};
+#[cfg(feature = "ark")]
+use casper_groth16::bn254;
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::SCALAR_FIELD_MODULUS;
+use casper_groth16::{VerifyError, BASE_FIELD_MODULUS};
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_types::U256;
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+fn base_field_modulus() -> U256 {
+    U256::from_str_radix(BASE_FIELD_MODULUS, 10).unwrap()
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn off_curve_point_is_rejected() {
+    let result = alt_bn128_add(U256::one(), U256::one(), U256::zero(), U256::zero());
+    assert_eq!(result, Err(VerifyError::PointNotOnCurve));
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn coordinate_outside_base_field_is_rejected() {
+    let result = alt_bn128_add(
+        base_field_modulus(),
+        U256::from(2),
+        U256::zero(),
+        U256::zero(),
+    );
+    assert_eq!(result, Err(VerifyError::CoordinateNotInField));
+
+    let result = alt_bn128_pairing(vec![(
+        U256::zero(),
+        base_field_modulus(),
+        U256::zero(),
+        U256::zero(),
+        U256::zero(),
+        U256::zero(),
+    )]);
+    assert_eq!(result, Err(VerifyError::CoordinateNotInField));
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn scalar_outside_scalar_field_is_rejected() {
+    let scalar = U256::from_str_radix(SCALAR_FIELD_MODULUS, 10).unwrap();
+    let result = alt_bn128_mul(U256::one(), U256::from(2), scalar);
+    assert_eq!(result, Err(VerifyError::ScalarOutOfRange));
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn input_count_mismatch_is_rejected() {
+    let generator = bn::AffineG1::new(bn::Fq::one(), bn::Fq::from_str("2").unwrap()).unwrap();
+    let result = compute_vk(vec![generator, generator], vec![]);
+    assert_eq!(
+        result,
+        Err(VerifyError::InputCountMismatch {
+            expected: 1,
+            got: 0
+        })
+    );
+    assert_eq!(
+        compute_vk(vec![], vec![]),
+        Err(VerifyError::EmptyVerifyingKey)
+    );
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn malformed_decimal_is_rejected_bn254() {
+    assert_eq!(
+        bn254::parse_biguint_to_fq("0x1234"),
+        Err(VerifyError::InvalidEncoding)
+    );
+    assert_eq!(
+        bn254::parse_biguint_to_fq(BASE_FIELD_MODULUS),
+        Err(VerifyError::CoordinateNotInField)
+    );
+}
@@ -0,0 +1,169 @@
+#![cfg(all(feature = "std", feature = "zeropool"))]
+
+// One G2 point written in every convention, checked against each consumer of that convention
+use casper_groth16::bn::{self, G2Point};
+use casper_groth16::snarkjs::SnarkjsProof;
+use casper_groth16::solidity::SolidityProof;
+use casper_types::U256;
+
+#[cfg(any(feature = "ark", not(feature = "casper")))]
+fn u256(value: &str) -> U256 {
+    U256::from_str_radix(value, 16).unwrap()
+}
+
+#[cfg(feature = "ark")]
+fn ark_u256(f: &ark_bn254::Fq) -> U256 {
+    U256::from_dec_str(&f.to_string()).unwrap()
+}
+
+// The generator of G2 as EIP-197 writes it, `[x.c1, x.c0]` and `[y.c1, y.c0]`
+#[cfg(any(feature = "ark", not(feature = "casper")))]
+fn generator_eip197() -> ([U256; 2], [U256; 2]) {
+    (
+        [
+            u256("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+            u256("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+        ],
+        [
+            u256("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+            u256("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+        ],
+    )
+}
+
+#[cfg(any(feature = "ark", not(feature = "casper")))]
+fn words(values: &[U256]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| {
+            let mut word = [0u8; 32];
+            value.to_big_endian(&mut word);
+            word
+        })
+        .collect()
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn every_convention_names_the_same_point() {
+    use ark_bn254::G2Affine;
+    use ark_ec::AffineRepr;
+    use casper_groth16::bn::Zeropool;
+    use casper_groth16::groth16::{swap_fq2_coefficients, Bn254Backend};
+
+    let (x, y) = generator_eip197();
+    let eip197 = G2Point::from_eip197(x, y);
+    let snarkjs = G2Point::from_snarkjs([x[1], x[0]], [y[1], y[0]]);
+    let arkworks = G2Point::from(&G2Affine::generator());
+    assert_eq!(eip197, snarkjs);
+    assert_eq!(eip197, arkworks);
+    assert_eq!(eip197, G2Point::new([x[1], x[0]], [y[1], y[0]]));
+
+    assert_eq!(eip197.to_eip197(), (x, y));
+    assert_eq!(eip197.to_snarkjs(), ([x[1], x[0]], [y[1], y[0]]));
+    assert_eq!(G2Affine::try_from(&eip197), Ok(G2Affine::generator()));
+
+    // the byte encodings swap the same way
+    let bytes = Zeropool::g2_to_bytes(&eip197);
+    assert_eq!(
+        swap_fq2_coefficients(&bytes).to_vec(),
+        words(&[x[0], x[1], y[0], y[1]])
+    );
+    assert_eq!(swap_fq2_coefficients(&swap_fq2_coefficients(&bytes)), bytes);
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn arkworks_points_keep_their_coefficients() {
+    use ark_bn254::{Fq2, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use casper_groth16::{VerifyError, BASE_FIELD_MODULUS};
+
+    let p = (G2Affine::generator() * ark_bn254::Fr::from(7u8)).into_affine();
+    let point = G2Point::from(&p);
+    assert_eq!(
+        point.to_snarkjs(),
+        (
+            [ark_u256(&p.x.c0), ark_u256(&p.x.c1)],
+            [ark_u256(&p.y.c0), ark_u256(&p.y.c1)]
+        )
+    );
+    assert_eq!(G2Affine::try_from(&point), Ok(p));
+    assert_eq!(
+        G2Point::from(&G2Affine::identity()),
+        G2Point::new([U256::zero(); 2], [U256::zero(); 2])
+    );
+
+    // in the field and in the wrong order, so off the twist
+    let swapped = G2Point::from_eip197(point.x, point.y);
+    let swapped = G2Affine::try_from(&swapped).unwrap();
+    assert_eq!(
+        swapped,
+        G2Affine::new_unchecked(Fq2::new(p.x.c1, p.x.c0), Fq2::new(p.y.c1, p.y.c0))
+    );
+    assert!(!swapped.is_on_curve());
+
+    let modulus = U256::from_dec_str(BASE_FIELD_MODULUS).unwrap();
+    assert_eq!(
+        G2Affine::try_from(&G2Point::from_snarkjs([modulus, U256::zero()], point.y)),
+        Err(VerifyError::CoordinateNotInField)
+    );
+}
+
+#[cfg(not(feature = "casper"))]
+#[test]
+fn pairing_inputs_follow_their_convention() {
+    use casper_groth16::bn::{alt_bn128_pairing, alt_bn128_pairing_check, G1Point, Zeropool};
+    use casper_groth16::precompile::ecpairing;
+    use casper_groth16::VerifyError;
+
+    // e(G1, G2) * e(-G1, G2) == 1
+    let g1 = G1Point::new(U256::one(), U256::from(2));
+    let neg_g1 = G1Point::new(
+        U256::one(),
+        u256("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45"),
+    );
+    let (x, y) = generator_eip197();
+    let g2 = G2Point::from_eip197(x, y);
+    assert_eq!(alt_bn128_pairing_check(&[(g1, g2), (neg_g1, g2)]), Ok(true));
+
+    // the tuples of `alt_bn128_pairing` are `[c0, c1]`
+    let (x, y) = g2.to_snarkjs();
+    let tuple = |p: G1Point| (p.x, p.y, x[0], x[1], y[0], y[1]);
+    assert_eq!(alt_bn128_pairing(vec![tuple(g1), tuple(neg_g1)]), Ok(true));
+
+    // `ECPAIRING` input is `[c1, c0]`
+    let (x, y) = g2.to_eip197();
+    let input = [
+        words(&[g1.x, g1.y, x[0], x[1], y[0], y[1]]),
+        words(&[neg_g1.x, neg_g1.y, x[0], x[1], y[0], y[1]]),
+    ]
+    .concat();
+    assert_eq!(ecpairing::<Zeropool>(&input).unwrap()[31], 1);
+
+    // fed in the other order the point is off the twist
+    let swapped = G2Point::from_snarkjs(x, y);
+    assert_eq!(
+        alt_bn128_pairing_check(&[(g1, swapped), (neg_g1, swapped)]),
+        Err(VerifyError::PointNotOnCurve)
+    );
+}
+
+#[test]
+fn loaders_use_the_named_conventions() {
+    let json = include_str!("data/multiplier2/proof.json");
+    let snarkjs = SnarkjsProof::from_json(json).unwrap();
+    let proof: bn::Proof = (&snarkjs).try_into().unwrap();
+    let coefficient = |i: usize, j: usize| U256::from_dec_str(&snarkjs.pi_b[i][j]).unwrap();
+    let b = G2Point::from_snarkjs(
+        [coefficient(0, 0), coefficient(0, 1)],
+        [coefficient(1, 0), coefficient(1, 1)],
+    );
+    assert_eq!(proof.b, b);
+
+    // Solidity calldata writes `b` as EIP-197 does
+    let solidity = SolidityProof::from(&proof);
+    let word = |w: &[u8; 32]| U256::from_big_endian(w);
+    let (x, y) = b.to_eip197();
+    assert_eq!(solidity.b.map(|c| c.map(|w| word(&w))), [x, y]);
+}
@@ -0,0 +1,98 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+mod common;
+
+use casper_groth16::{bn254, VerifyError};
+use common::{Circuit, Fixture};
+
+fn verify(
+    fixture: &Fixture,
+    proof: &bn254::Proof,
+    inputs: &bn254::PublicInputs,
+) -> Result<bool, VerifyError> {
+    let expected = bn254::verify(&fixture.vk, proof, inputs);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    {
+        use casper_groth16::bn;
+        use common::{bn_inputs, bn_proof, bn_vk};
+
+        let vk = bn_vk(&fixture.vk);
+        assert_eq!(
+            bn::verify(&vk, &bn_proof(proof), &bn_inputs(inputs)),
+            expected
+        );
+    }
+    expected
+}
+
+fn check(circuit: Circuit) {
+    let fixture = Fixture::new(circuit);
+    assert_eq!(
+        verify(&fixture, &fixture.proof, &fixture.inputs),
+        Ok(true),
+        "{circuit:?}"
+    );
+    assert_eq!(
+        verify(&fixture, &fixture.tampered_proof(), &fixture.inputs),
+        Ok(false),
+        "{circuit:?}"
+    );
+    for i in 0..fixture.inputs.len() {
+        assert_eq!(
+            verify(&fixture, &fixture.proof, &fixture.tampered_inputs(i)),
+            Ok(false),
+            "{circuit:?} input {i}"
+        );
+    }
+}
+
+#[test]
+fn generated_multiplier() {
+    check(Circuit::Multiplier { a: 3, b: 11 });
+}
+
+#[test]
+fn generated_range_check() {
+    check(Circuit::RangeCheck {
+        value: 1000,
+        bits: 10,
+    });
+}
+
+#[test]
+fn generated_many_inputs() {
+    check(Circuit::ManyInputs { w: 7, count: 16 });
+}
+
+#[test]
+fn generated_no_inputs() {
+    let fixture = Fixture::new(Circuit::NoInputs { a: 5, b: 9 });
+    assert_eq!(fixture.vk.ic.len(), 1);
+    check(Circuit::NoInputs { a: 5, b: 9 });
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_verifier_wraps_verify() {
+    let fixture = Fixture::new(Circuit::Multiplier { a: 3, b: 11 });
+    let old = |proof: &bn254::Proof, inputs: &bn254::PublicInputs| {
+        bn254::verify_groth16_proof(
+            proof.a,
+            proof.b,
+            proof.c,
+            fixture.vk.alpha1,
+            fixture.vk.beta2,
+            fixture.vk.gamma2,
+            fixture.vk.delta2,
+            fixture.vk.ic.clone(),
+            inputs.0.clone(),
+        )
+    };
+    assert!(old(&fixture.proof, &fixture.inputs));
+    assert!(!old(&fixture.tampered_proof(), &fixture.inputs));
+    assert!(!old(&fixture.proof, &fixture.tampered_inputs(0)));
+
+    let mut inputs = fixture.inputs.clone();
+    inputs.0.push(1u32.into());
+    assert!(!old(&fixture.proof, &inputs));
+}
@@ -0,0 +1,426 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+// gnark files are written here from the fixtures in `tests/common`, compressed as by `WriteTo` and
+// uncompressed as by `WriteRawTo`.
+mod common;
+
+use ark_bn254::{Fq, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn;
+use casper_groth16::gnark::{GnarkG1, GnarkProof, GnarkPublicWitness, GnarkVerifyingKey};
+use casper_groth16::{bn254, VerifyError};
+use common::{Circuit, CommittedFixture, Fixture};
+
+fn fq_bytes(value: &Fq) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+    bytes
+}
+
+fn largest(value: &Fq) -> bool {
+    value.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+fn g1(p: &G1Affine, compressed: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match (p.is_zero(), compressed) {
+        (true, true) => {
+            bytes.resize(32, 0);
+            bytes[0] = 0b01 << 6;
+        }
+        (true, false) => bytes.resize(64, 0),
+        (false, true) => {
+            bytes.extend_from_slice(&fq_bytes(&p.x));
+            bytes[0] |= if largest(&p.y) { 0b11 << 6 } else { 0b10 << 6 };
+        }
+        (false, false) => {
+            bytes.extend_from_slice(&fq_bytes(&p.x));
+            bytes.extend_from_slice(&fq_bytes(&p.y));
+        }
+    }
+    bytes
+}
+
+fn g2(p: &G2Affine, compressed: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    if p.is_zero() {
+        bytes.resize(if compressed { 64 } else { 128 }, 0);
+        if compressed {
+            bytes[0] = 0b01 << 6;
+        }
+        return bytes;
+    }
+    bytes.extend_from_slice(&fq_bytes(&p.x.c1));
+    bytes.extend_from_slice(&fq_bytes(&p.x.c0));
+    if compressed {
+        let y_largest = if p.y.c1.is_zero() {
+            largest(&p.y.c0)
+        } else {
+            largest(&p.y.c1)
+        };
+        bytes[0] |= if y_largest { 0b11 << 6 } else { 0b10 << 6 };
+    } else {
+        bytes.extend_from_slice(&fq_bytes(&p.y.c1));
+        bytes.extend_from_slice(&fq_bytes(&p.y.c0));
+    }
+    bytes
+}
+
+fn vk_bytes(vk: &bn254::VerifyingKey, compressed: bool) -> Vec<u8> {
+    // `[beta]1` and `[delta]1` are only read by gnark's prover, any point will do
+    let unused = G1Affine::generator();
+    let mut bytes = Vec::new();
+    bytes.extend(g1(&vk.alpha1, compressed));
+    bytes.extend(g1(&unused, compressed));
+    bytes.extend(g2(&vk.beta2, compressed));
+    bytes.extend(g2(&vk.gamma2, compressed));
+    bytes.extend(g1(&unused, compressed));
+    bytes.extend(g2(&vk.delta2, compressed));
+    bytes.extend((vk.ic.len() as u32).to_be_bytes());
+    for p in &vk.ic {
+        bytes.extend(g1(p, compressed));
+    }
+    // no commitments: `PublicAndCommitmentCommitted` and `CommitmentKeys` are empty
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend(0u32.to_be_bytes());
+    bytes
+}
+
+fn committed_vk_bytes(vk: &bn254::CommittedVerifyingKey, compressed: bool) -> Vec<u8> {
+    let mut bytes = vk_bytes(&vk.vk, compressed);
+    bytes.truncate(bytes.len() - 8);
+    bytes.extend((vk.public_committed.len() as u32).to_be_bytes());
+    for committed in &vk.public_committed {
+        bytes.extend((committed.len() as u32).to_be_bytes());
+        for index in committed {
+            bytes.extend((*index as u64).to_be_bytes());
+        }
+    }
+    bytes.extend((vk.commitment_keys.len() as u32).to_be_bytes());
+    for key in &vk.commitment_keys {
+        bytes.extend(g2(&key.g, compressed));
+        bytes.extend(g2(&key.g_sigma_neg, compressed));
+    }
+    bytes
+}
+
+fn proof_bytes(proof: &bn254::Proof, compressed: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(g1(&proof.a, compressed));
+    bytes.extend(g2(&proof.b, compressed));
+    bytes.extend(g1(&proof.c, compressed));
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend(g1(&G1Affine::zero(), compressed));
+    bytes
+}
+
+fn committed_proof_bytes(proof: &bn254::CommittedProof, compressed: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(g1(&proof.proof.a, compressed));
+    bytes.extend(g2(&proof.proof.b, compressed));
+    bytes.extend(g1(&proof.proof.c, compressed));
+    bytes.extend((proof.commitments.len() as u32).to_be_bytes());
+    for commitment in &proof.commitments {
+        bytes.extend(g1(commitment, compressed));
+    }
+    bytes.extend(g1(&proof.commitment_pok, compressed));
+    bytes
+}
+
+fn witness_bytes(inputs: &bn254::PublicInputs) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend((inputs.len() as u32).to_be_bytes());
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend((inputs.len() as u32).to_be_bytes());
+    for input in inputs.iter() {
+        let input = input.to_bytes_be();
+        bytes.extend(vec![0; 32 - input.len()]);
+        bytes.extend(input);
+    }
+    bytes
+}
+
+#[test]
+fn gnark_files_verify_on_both_backends() {
+    for circuit in [
+        Circuit::Multiplier { a: 3, b: 11 },
+        Circuit::ManyInputs { w: 7, count: 5 },
+        Circuit::NoInputs { a: 2, b: 9 },
+    ] {
+        let fixture = Fixture::new(circuit);
+        let witness = GnarkPublicWitness::from_bytes(&witness_bytes(&fixture.inputs)).unwrap();
+        for compressed in [true, false] {
+            let vk = GnarkVerifyingKey::from_bytes(&vk_bytes(&fixture.vk, compressed)).unwrap();
+            let proof = GnarkProof::from_bytes(&proof_bytes(&fixture.proof, compressed)).unwrap();
+
+            let bn254_vk = bn254::VerifyingKey::try_from(&vk).unwrap();
+            let bn254_proof = bn254::Proof::try_from(&proof).unwrap();
+            assert_eq!(bn254_vk, fixture.vk, "{circuit:?}");
+            assert_eq!(bn254_proof, fixture.proof, "{circuit:?}");
+            let bn254_inputs = bn254::PublicInputs::from(&witness);
+            assert_eq!(bn254_inputs, fixture.inputs);
+            assert_eq!(
+                bn254::verify(&bn254_vk, &bn254_proof, &bn254_inputs),
+                Ok(true),
+                "{circuit:?}"
+            );
+
+            #[cfg(feature = "zeropool")]
+            {
+                let bn_vk = bn::VerifyingKey::try_from(&vk).unwrap();
+                let bn_proof = bn::Proof::try_from(&proof).unwrap();
+                assert_eq!(bn_vk, common::bn_vk(&fixture.vk));
+                assert_eq!(bn_proof, common::bn_proof(&fixture.proof));
+                #[cfg(not(feature = "casper"))]
+                assert_eq!(
+                    bn::verify(&bn_vk, &bn_proof, &bn::PublicInputs::from(&witness)),
+                    Ok(true),
+                    "{circuit:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn gnark_tampered_proof_is_rejected() {
+    let fixture = Fixture::new(Circuit::Multiplier { a: 3, b: 11 });
+    let vk = GnarkVerifyingKey::from_bytes(&vk_bytes(&fixture.vk, true)).unwrap();
+    let proof = GnarkProof::from_bytes(&proof_bytes(&fixture.tampered_proof(), true)).unwrap();
+    let witness = GnarkPublicWitness::from_bytes(&witness_bytes(&fixture.inputs)).unwrap();
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(
+        bn::verify(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&witness).into()
+        ),
+        Ok(false)
+    );
+    assert_eq!(
+        bn254::verify(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&witness).into()
+        ),
+        Ok(false)
+    );
+}
+
+#[test]
+fn gnark_compressed_generator() {
+    // (1, 2) with the smaller `y`, and its negation (1, p - 2) with the larger one
+    let mut generator = [0u8; 32];
+    generator[31] = 1;
+    generator[0] = 0b10 << 6;
+    let mut negated = generator;
+    negated[0] = 0b11 << 6;
+    assert_eq!(g1(&G1Affine::generator(), true), generator);
+    assert_eq!(g1(&-G1Affine::generator(), true), negated);
+
+    let mut proof = proof_bytes(
+        &Fixture::new(Circuit::Multiplier { a: 3, b: 11 }).proof,
+        true,
+    );
+    proof[..32].copy_from_slice(&negated);
+    let proof = GnarkProof::from_bytes(&proof).unwrap();
+    let mut x = [0u8; 32];
+    x[31] = 1;
+    assert_eq!(proof.ar, GnarkG1::Compressed { x, largest: true });
+    assert_eq!(
+        bn254::Proof::try_from(&proof).unwrap().a,
+        -G1Affine::generator()
+    );
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::Proof::try_from(&proof).unwrap().a,
+        bn::G1Point::new(
+            1.into(),
+            bn::fq_to_u256(-bn::fq_from_u256(2.into()).unwrap())
+        )
+    );
+}
+
+// A proof with `A = G1`, `B = G2` and `C = -G1` written out by hand from gnark's layout rather
+// than by the encoder above. The compressed G2 point is `x.c1 || x.c0` with the flag in the top bits
+// of `x.c1`, `y.c1` of the generator is below `(p - 1) / 2`, so the flag is `0b10`.
+#[test]
+fn gnark_generator_bytes() {
+    let compressed = "8000000000000000000000000000000000000000000000000000000000000001\
+                      998e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                      1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                      c000000000000000000000000000000000000000000000000000000000000001\
+                      00000000\
+                      4000000000000000000000000000000000000000000000000000000000000000";
+    let raw = "0000000000000000000000000000000000000000000000000000000000000001\
+               0000000000000000000000000000000000000000000000000000000000000002\
+               198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+               1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+               090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+               12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa\
+               0000000000000000000000000000000000000000000000000000000000000001\
+               30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45\
+               00000000\
+               0000000000000000000000000000000000000000000000000000000000000000\
+               0000000000000000000000000000000000000000000000000000000000000000";
+    let hex = |hex: &str| -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    };
+    // the encoder the other tests use writes the same bytes
+    let generators = bn254::Proof {
+        a: G1Affine::generator(),
+        b: G2Affine::generator(),
+        c: -G1Affine::generator(),
+    };
+    assert_eq!(proof_bytes(&generators, true), hex(compressed));
+    assert_eq!(proof_bytes(&generators, false), hex(raw));
+
+    for bytes in [hex(compressed), hex(raw)] {
+        let proof = GnarkProof::from_bytes(&bytes).unwrap();
+        let bn254_proof = bn254::Proof::try_from(&proof).unwrap();
+        assert_eq!(bn254_proof.a, G1Affine::generator());
+        assert_eq!(bn254_proof.b, G2Affine::generator());
+        assert_eq!(bn254_proof.c, -G1Affine::generator());
+        #[cfg(feature = "zeropool")]
+        assert_eq!(
+            bn::Proof::try_from(&proof).unwrap(),
+            bn::Proof {
+                a: bn::G1Point::new(1.into(), 2.into()),
+                b: bn::G2Point::from(&G2Affine::generator()),
+                c: bn::G1Point::new(
+                    1.into(),
+                    bn::fq_to_u256(-bn::fq_from_u256(2.into()).unwrap())
+                ),
+            }
+        );
+    }
+}
+
+#[test]
+fn gnark_commitments_need_the_committed_types() {
+    let fixture = CommittedFixture::new(8, 2, vec![vec![1], vec![2]]);
+    let witness = GnarkPublicWitness::from_bytes(&witness_bytes(&fixture.inputs)).unwrap();
+    for compressed in [true, false] {
+        let vk =
+            GnarkVerifyingKey::from_bytes(&committed_vk_bytes(&fixture.vk, compressed)).unwrap();
+        let proof =
+            GnarkProof::from_bytes(&committed_proof_bytes(&fixture.proof, compressed)).unwrap();
+        assert_eq!(vk.public_and_commitment_committed, vec![vec![1], vec![2]]);
+        assert_eq!(vk.commitment_keys.len(), 2);
+        assert_eq!(proof.commitments.len(), 2);
+
+        #[cfg(feature = "zeropool")]
+        assert_eq!(
+            bn::VerifyingKey::try_from(&vk),
+            Err(VerifyError::Unsupported)
+        );
+        assert_eq!(
+            bn254::VerifyingKey::try_from(&vk),
+            Err(VerifyError::Unsupported)
+        );
+        #[cfg(feature = "zeropool")]
+        assert_eq!(bn::Proof::try_from(&proof), Err(VerifyError::Unsupported));
+        assert_eq!(
+            bn254::Proof::try_from(&proof),
+            Err(VerifyError::Unsupported)
+        );
+
+        let bn254_vk = bn254::CommittedVerifyingKey::try_from(&vk).unwrap();
+        let bn254_proof = bn254::CommittedProof::try_from(&proof).unwrap();
+        assert_eq!(bn254_vk, fixture.vk);
+        assert_eq!(bn254_proof, fixture.proof);
+        assert_eq!(
+            bn254::verify_committed(&bn254_vk, &bn254_proof, &(&witness).into()),
+            Ok(true)
+        );
+        #[cfg(feature = "zeropool")]
+        {
+            let bn_vk = bn::CommittedVerifyingKey::try_from(&vk).unwrap();
+            let bn_proof = bn::CommittedProof::try_from(&proof).unwrap();
+            assert_eq!(bn_vk, fixture.bn_vk());
+            assert_eq!(bn_proof, fixture.bn_proof());
+            #[cfg(not(feature = "casper"))]
+            assert_eq!(
+                bn::verify_committed(&bn_vk, &bn_proof, &(&witness).into()),
+                Ok(true)
+            );
+        }
+    }
+}
+
+#[test]
+fn gnark_rejects_malformed_encodings() {
+    let fixture = Fixture::new(Circuit::Multiplier { a: 3, b: 11 });
+    let vk = vk_bytes(&fixture.vk, true);
+    let proof = proof_bytes(&fixture.proof, false);
+
+    assert_eq!(
+        GnarkVerifyingKey::from_bytes(&vk[..vk.len() - 1]),
+        Err(VerifyError::InvalidEncoding)
+    );
+    assert_eq!(
+        GnarkProof::from_bytes(&[proof.as_slice(), &[0]].concat()),
+        Err(VerifyError::InvalidEncoding)
+    );
+
+    // compressed infinity with a non-zero coordinate
+    let mut bad = proof.clone();
+    let pok = bad.len() - 64;
+    bad.truncate(pok);
+    bad.extend([0b01 << 6].into_iter().chain([0; 30]).chain([1]));
+    assert_eq!(
+        GnarkProof::from_bytes(&bad),
+        Err(VerifyError::InvalidEncoding)
+    );
+
+    // a public witness with secret values
+    let mut witness = witness_bytes(&fixture.inputs);
+    witness[7] = 1;
+    assert_eq!(
+        GnarkPublicWitness::from_bytes(&witness),
+        Err(VerifyError::Unsupported)
+    );
+}
+
+#[test]
+fn gnark_decompression_errors() {
+    let fixture = Fixture::new(Circuit::Multiplier { a: 3, b: 11 });
+    let mut proof = proof_bytes(&fixture.proof, true);
+
+    // the smallest `x` for which `x^3 + 3` has no square root
+    let x = (1u64..)
+        .map(Fq::from)
+        .find(|x| (*x * x * x + Fq::from(3u8)).sqrt().is_none())
+        .unwrap();
+    proof[..32].copy_from_slice(&fq_bytes(&x));
+    proof[0] |= 0b10 << 6;
+    let gnark = GnarkProof::from_bytes(&proof).unwrap();
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::Proof::try_from(&gnark),
+        Err(VerifyError::PointNotOnCurve)
+    );
+    assert_eq!(
+        bn254::Proof::try_from(&gnark),
+        Err(VerifyError::PointNotOnCurve)
+    );
+
+    // `x = p`
+    proof[..32].copy_from_slice(&Fq::MODULUS.to_bytes_be());
+    proof[0] |= 0b10 << 6;
+    let gnark = GnarkProof::from_bytes(&proof).unwrap();
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::Proof::try_from(&gnark),
+        Err(VerifyError::CoordinateNotInField)
+    );
+    assert_eq!(
+        bn254::Proof::try_from(&gnark),
+        Err(VerifyError::CoordinateNotInField)
+    );
+}
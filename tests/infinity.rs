@@ -0,0 +1,276 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+// `(0, 0)` is the point at infinity in every encoding, and both backends read it as the identity
+mod common;
+
+use ark_bn254::{Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{PrimeField, Zero};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn::{self, G1Point, G2Point, Zeropool};
+use casper_groth16::bn254::{self, Arkworks};
+use casper_groth16::groth16::{Bn254Backend, G1_BYTES, G2_BYTES};
+use casper_groth16::snarkjs::SnarkjsProof;
+use casper_groth16::{types, VerifyError};
+#[cfg(feature = "zeropool")]
+use casper_types::U256;
+use common::{Circuit, Fixture};
+use num_bigint::BigUint;
+
+/// `common::simulate` with a fixed trapdoor, so the key depends on `ic` alone.
+fn simulate(ic: &[u64], inputs: &[Fr], a: Fr) -> (bn254::VerifyingKey, bn254::Proof) {
+    let mut rng = StdRng::seed_from_u64(0x6964_656e_7469_7479);
+    let [alpha, beta, gamma, delta, b] = [(); 5].map(|_| Fr::rand(&mut rng));
+    common::simulate(
+        [alpha, beta, gamma, delta, a, b],
+        &fr(ic),
+        inputs,
+        Fr::zero(),
+    )
+}
+
+fn fr(values: &[u64]) -> Vec<Fr> {
+    values.iter().map(|x| Fr::from(*x)).collect()
+}
+
+fn public_inputs(inputs: &[Fr]) -> bn254::PublicInputs {
+    types::PublicInputs(
+        inputs
+            .iter()
+            .map(|x| BigUint::from(x.into_bigint()))
+            .collect(),
+    )
+}
+
+// Every entry point, with the result both backends agree on
+fn verify_everywhere(
+    vk: &bn254::VerifyingKey,
+    proof: &bn254::Proof,
+    inputs: &bn254::PublicInputs,
+) -> Result<bool, VerifyError> {
+    let result = bn254::verify(vk, proof, inputs);
+    let pvk = bn254::PreparedVerifyingKey::new(vk.clone()).unwrap();
+    assert_eq!(bn254::verify_prepared(&pvk, proof, inputs), result);
+    let batch = bn254::verify_batch(vk, &[(*proof, inputs.clone())]);
+    assert_eq!(batch.map(|invalid| invalid.is_none()), result);
+
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    {
+        use common::{bn_inputs, bn_proof, bn_vk};
+
+        let (vk, proof, inputs) = (bn_vk(vk), bn_proof(proof), bn_inputs(inputs));
+        assert_eq!(bn::verify(&vk, &proof, &inputs), result);
+        let pvk = bn::PreparedVerifyingKey::new(vk.clone()).unwrap();
+        assert_eq!(bn::verify_prepared(&pvk, &proof, &inputs), result);
+        let batch = bn::verify_batch(&vk, &[(proof, inputs)]);
+        assert_eq!(batch.map(|invalid| invalid.is_none()), result);
+    }
+    result
+}
+
+#[test]
+fn every_encoding_decodes_to_the_identity() {
+    assert_eq!(
+        Arkworks::g1_from_bytes(&[0; G1_BYTES]),
+        Ok(G1Affine::identity())
+    );
+    assert_eq!(
+        Arkworks::g2_from_bytes(&[0; G2_BYTES]),
+        Ok(G2Affine::identity())
+    );
+    assert_eq!(Arkworks::g1_to_bytes(&G1Affine::identity()), [0; G1_BYTES]);
+    assert_eq!(Arkworks::g2_to_bytes(&G2Affine::identity()), [0; G2_BYTES]);
+    #[cfg(feature = "zeropool")]
+    let zero = G1Point::new(U256::zero(), U256::zero());
+    #[cfg(feature = "zeropool")]
+    {
+        assert_eq!(Zeropool::g1_from_bytes(&[0; G1_BYTES]), Ok(zero));
+        assert_eq!(Zeropool::validate_g1(&zero), Ok(()));
+        assert_eq!(
+            Zeropool::validate_g2(&G2Point::new([U256::zero(); 2], [U256::zero(); 2])),
+            Ok(())
+        );
+    }
+
+    // coordinates, which `new_unchecked` would leave off the curve
+    assert_eq!(
+        bn254::g1_from_coords(Fq::zero(), Fq::zero()),
+        Ok(G1Affine::identity())
+    );
+    assert_eq!(
+        bn254::g2_from_coords(Fq2::zero(), Fq2::zero()),
+        Ok(G2Affine::identity())
+    );
+    assert!(!G1Affine::new_unchecked(Fq::zero(), Fq::zero()).is_on_curve());
+
+    // snarkjs writes `z = 0`, the coordinates beside it may be anything
+    let mut json = SnarkjsProof::from_json(include_str!("data/multiplier2/proof.json")).unwrap();
+    json.pi_a = ["1".into(), "1".into(), "0".into()];
+    json.pi_c = ["0".into(), "0".into(), "1".into()];
+    let proof = bn254::Proof::try_from(&json).unwrap();
+    assert_eq!(proof.a, G1Affine::identity());
+    assert_eq!(proof.c, G1Affine::identity());
+    #[cfg(feature = "zeropool")]
+    {
+        let proof = bn::Proof::try_from(&json).unwrap();
+        assert_eq!((proof.a, proof.c), (zero, zero));
+    }
+}
+
+#[test]
+fn group_operations_treat_infinity_as_the_identity() {
+    let g = G1Affine::generator();
+    assert_eq!(
+        bn254::negate_g1_affine(G1Affine::identity()),
+        G1Affine::identity()
+    );
+    assert_eq!(
+        bn254::negate_g1_affine(G1Affine::new_unchecked(Fq::zero(), Fq::zero())),
+        G1Affine::identity()
+    );
+    assert_eq!(bn254::negate_g1_affine(g), -g);
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::negate_g1_affine(U256::zero(), U256::zero()),
+        Ok((U256::zero(), U256::zero()))
+    );
+
+    let (x, y) = bn254::extract_g1_coordinates(g);
+    let zero = BigUint::ZERO;
+    assert_eq!(
+        bn254::add_g1_as_coordinates(zero.clone(), zero.clone(), x.clone(), y.clone()),
+        Ok(g)
+    );
+    assert_eq!(
+        bn254::add_g1_as_coordinates(x, y, zero.clone(), zero.clone()),
+        Ok(g)
+    );
+    let sum = bn254::add_g1_as_coordinates(zero.clone(), zero.clone(), zero.clone(), zero);
+    assert_eq!(sum, Ok(G1Affine::identity()));
+    assert_eq!(
+        bn254::extract_g1_coordinates(sum.unwrap()),
+        (BigUint::ZERO, BigUint::ZERO)
+    );
+
+    // `P + (-P)`, `0 + 0` and `0 * P` are written as zeros by both backends
+    for (p, q) in [(g, -g), (G1Affine::identity(), G1Affine::identity())] {
+        let bytes = Arkworks::g1_to_bytes(&Arkworks::g1_add(&p, &q).unwrap());
+        assert_eq!(bytes, [0; G1_BYTES]);
+        #[cfg(feature = "zeropool")]
+        {
+            let (p, q) = (
+                Zeropool::g1_from_bytes(&Arkworks::g1_to_bytes(&p)).unwrap(),
+                Zeropool::g1_from_bytes(&Arkworks::g1_to_bytes(&q)).unwrap(),
+            );
+            assert_eq!(
+                Zeropool::g1_to_bytes(&Zeropool::g1_add(&p, &q).unwrap()),
+                bytes
+            );
+        }
+    }
+    let product = Arkworks::g1_mul(&g, &Fr::zero()).unwrap();
+    assert_eq!(Arkworks::g1_to_bytes(&product), [0; G1_BYTES]);
+    #[cfg(feature = "zeropool")]
+    {
+        let g = Zeropool::g1_from_bytes(&Arkworks::g1_to_bytes(&g)).unwrap();
+        let product = Zeropool::g1_mul(&g, &Zeropool::scalar_zero()).unwrap();
+        assert_eq!(Zeropool::g1_to_bytes(&product), [0; G1_BYTES]);
+    }
+}
+
+#[test]
+fn identity_ic_points_verify() {
+    // `IC0` and `IC2` at infinity
+    let (vk, proof) = simulate(&[0, 5, 0], &fr(&[3, 9]), Fr::from(7u8));
+    assert!(vk.ic[0].is_zero() && vk.ic[2].is_zero());
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[3, 9]))),
+        Ok(true)
+    );
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[4, 9]))),
+        Ok(false)
+    );
+    // an input weighted by an identity point does not change `vk_x`
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[3, 10]))),
+        Ok(true)
+    );
+
+    // every IC point at infinity, so `vk_x` is too
+    let (vk, proof) = simulate(&[0, 0], &fr(&[8]), Fr::from(7u8));
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[8]))),
+        Ok(true)
+    );
+}
+
+#[test]
+fn zero_public_inputs_verify() {
+    for circuit in [
+        Circuit::Multiplier { a: 0, b: 11 },
+        Circuit::ManyInputs { w: 0, count: 3 },
+    ] {
+        let fixture = Fixture::new(circuit);
+        assert!(fixture.inputs.iter().all(|input| *input == BigUint::ZERO));
+        assert_eq!(
+            verify_everywhere(&fixture.vk, &fixture.proof, &fixture.inputs),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_everywhere(&fixture.vk, &fixture.proof, &fixture.tampered_inputs(0)),
+            Ok(false)
+        );
+    }
+
+    // `vk_x` lands on infinity when the inputs cancel `IC0`
+    let inputs = [-Fr::from(2u8)];
+    let (vk, proof) = simulate(&[2, 1], &inputs, Fr::from(7u8));
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&inputs)),
+        Ok(true)
+    );
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[0]))),
+        Ok(false)
+    );
+}
+
+#[test]
+fn identity_proof_elements_are_rejected() {
+    let fixture = Fixture::new(Circuit::Multiplier { a: 3, b: 11 });
+    let (vk, inputs) = (&fixture.vk, &fixture.inputs);
+    let at_infinity = Err(VerifyError::PointAtInfinity);
+    for proof in [
+        types::Proof {
+            a: G1Affine::identity(),
+            ..fixture.proof
+        },
+        types::Proof {
+            b: G2Affine::identity(),
+            ..fixture.proof
+        },
+        types::Proof {
+            c: G1Affine::identity(),
+            ..fixture.proof
+        },
+    ] {
+        assert_eq!(verify_everywhere(vk, &proof, inputs), at_infinity);
+    }
+
+    // with `A` at infinity `e(A, B)` drops out, and knowing the trapdoor `C` alone satisfies the
+    // pairing equation
+    let (vk, proof) = simulate(&[1, 2], &fr(&[5]), Fr::zero());
+    assert!(proof.a.is_zero());
+    assert_eq!(
+        verify_everywhere(&vk, &proof, &public_inputs(&fr(&[5]))),
+        at_infinity
+    );
+    let (_, honest) = simulate(&[1, 2], &fr(&[5]), Fr::from(7u8));
+    assert_eq!(
+        verify_everywhere(&vk, &honest, &public_inputs(&fr(&[5]))),
+        Ok(true)
+    );
+}
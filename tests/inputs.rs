@@ -0,0 +1,118 @@
+#![cfg(all(feature = "std", any(feature = "ark", not(feature = "casper"))))]
+
+mod common;
+
+use casper_groth16::snarkjs::SnarkjsPublicSignals;
+use casper_groth16::types::{InputPolicy, VerifyOptions};
+use casper_groth16::VerifyError;
+use common::snarkjs;
+
+// 33 + r, the only valid public signal of the multiplier circuit shifted by the field order
+fn aliased_signal() -> SnarkjsPublicSignals {
+    SnarkjsPublicSignals(vec![
+        "21888242871839275222246405745257275088548364400416034343698204186575808495650".to_string(),
+    ])
+}
+
+fn reduce() -> VerifyOptions {
+    VerifyOptions {
+        inputs: InputPolicy::Reduce,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn non_canonical_input_is_rejected_by_default() {
+    let (vk, proof, _) = snarkjs::multiplier2();
+    let signals = aliased_signal();
+
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    {
+        use casper_groth16::bn;
+
+        let result = bn::verify(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&signals).try_into().unwrap(),
+        );
+        assert_eq!(result, Err(VerifyError::ScalarOutOfRange));
+    }
+
+    #[cfg(feature = "ark")]
+    {
+        use casper_groth16::bn254;
+
+        let result = bn254::verify(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&signals).try_into().unwrap(),
+        );
+        assert_eq!(result, Err(VerifyError::ScalarOutOfRange));
+    }
+}
+
+#[test]
+fn non_canonical_input_is_reduced_on_request() {
+    let (vk, proof, _) = snarkjs::multiplier2();
+    let signals = aliased_signal();
+
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    {
+        use casper_groth16::bn;
+
+        let result = bn::verify_with_options(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&signals).try_into().unwrap(),
+            &reduce(),
+        );
+        assert_eq!(result, Ok(true));
+    }
+
+    #[cfg(feature = "ark")]
+    {
+        use casper_groth16::bn254;
+
+        let result = bn254::verify_with_options(
+            &(&vk).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&signals).try_into().unwrap(),
+            &reduce(),
+        );
+        assert_eq!(result, Ok(true));
+    }
+}
+
+#[cfg(all(feature = "ark", feature = "zeropool"))]
+#[test]
+fn canonical_inputs_agree_across_backends() {
+    use casper_groth16::{bn, bn254, SCALAR_FIELD_MODULUS};
+    use casper_types::U256;
+    use num_bigint::BigUint;
+
+    let r = U256::from_dec_str(SCALAR_FIELD_MODULUS).unwrap();
+    let inputs = bn::PublicInputs::from(vec![U256::zero(), r - 1, r, U256::MAX]);
+
+    assert_eq!(
+        bn::canonical_inputs(&inputs, InputPolicy::RejectNonCanonical),
+        Err(VerifyError::ScalarOutOfRange)
+    );
+    let reduced = bn::canonical_inputs(&inputs, InputPolicy::Reduce).unwrap();
+
+    let big_inputs = bn254::PublicInputs::from(
+        inputs
+            .iter()
+            .map(|input| BigUint::parse_bytes(input.to_string().as_bytes(), 10).unwrap())
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        bn254::canonical_inputs(&big_inputs, InputPolicy::RejectNonCanonical),
+        Err(VerifyError::ScalarOutOfRange)
+    );
+    let big_reduced = bn254::canonical_inputs(&big_inputs, InputPolicy::Reduce).unwrap();
+
+    let reduced: Vec<String> = reduced.iter().map(ToString::to_string).collect();
+    let big_reduced: Vec<String> = big_reduced.iter().map(ToString::to_string).collect();
+    assert_eq!(reduced, big_reduced);
+    assert_eq!(reduced[2], "0");
+}
@@ -0,0 +1,131 @@
+use casper_groth16::groth16::Bn254Backend;
+use casper_groth16::precompile::{ecadd, ecmul, ecpairing};
+use casper_groth16::VerifyError;
+
+fn hex(value: &str) -> Vec<u8> {
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const G1: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                  0000000000000000000000000000000000000000000000000000000000000002";
+const G1_NEG: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                      30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+const G1_DOUBLE: &str = "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+                         15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+// the group order r, r + 2 and (2^256 - 1) mod r
+const R: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+const R_PLUS_TWO: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000003";
+const MAX_REDUCED: &str = "0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffa";
+// x.c1 || x.c0 || y.c1 || y.c0
+const G2: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                  1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                  090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+                  12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+fn check_backend<B: Bn254Backend>() {
+    let g1 = hex(G1);
+    let double = hex(G1_DOUBLE);
+
+    assert_eq!(
+        ecadd::<B>(&[g1.clone(), g1.clone()].concat())
+            .unwrap()
+            .to_vec(),
+        double
+    );
+    // the second point is padded to infinity
+    assert_eq!(ecadd::<B>(&g1).unwrap().to_vec(), g1);
+    assert_eq!(ecadd::<B>(&[]).unwrap(), [0u8; 64]);
+    // trailing bytes are ignored
+    let mut long = [g1.clone(), g1.clone()].concat();
+    long.extend([0xff; 7]);
+    assert_eq!(ecadd::<B>(&long).unwrap().to_vec(), double);
+
+    let mut two = vec![0u8; 32];
+    two[31] = 2;
+    assert_eq!(
+        ecmul::<B>(&[g1.clone(), two].concat()).unwrap().to_vec(),
+        double
+    );
+    // full 256 bit scalars are reduced modulo the group order
+    let r_plus_two = hex(R_PLUS_TWO);
+    assert_eq!(
+        ecmul::<B>(&[g1.clone(), r_plus_two].concat())
+            .unwrap()
+            .to_vec(),
+        double
+    );
+    assert_eq!(
+        ecmul::<B>(&[g1.clone(), hex(R)].concat()).unwrap(),
+        [0u8; 64]
+    );
+    let max = vec![0xff; 32];
+    let max_reduced = hex(MAX_REDUCED);
+    assert_eq!(
+        ecmul::<B>(&[g1.clone(), max].concat()),
+        ecmul::<B>(&[g1.clone(), max_reduced].concat())
+    );
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let g2 = hex(G2);
+    assert_eq!(ecpairing::<B>(&[]), Ok(one));
+    let valid = [g1.clone(), g2.clone(), hex(G1_NEG), g2.clone()].concat();
+    assert_eq!(ecpairing::<B>(&valid), Ok(one));
+    let invalid = [g1.clone(), g2.clone(), g1.clone(), g2.clone()].concat();
+    assert_eq!(ecpairing::<B>(&invalid), Ok([0u8; 32]));
+    assert_eq!(
+        ecpairing::<B>(&valid[..valid.len() - 1]),
+        Err(VerifyError::InvalidEncoding)
+    );
+}
+
+fn check_invalid_points<B: Bn254Backend>() {
+    let g1 = hex(G1);
+    let mut off_curve = g1.clone();
+    off_curve[63] = 3;
+    assert_eq!(
+        ecadd::<B>(&[g1.clone(), off_curve.clone()].concat()),
+        Err(VerifyError::PointNotOnCurve)
+    );
+    assert_eq!(ecmul::<B>(&off_curve), Err(VerifyError::PointNotOnCurve));
+
+    // p itself is not a canonical coordinate
+    let mut not_in_field = hex(G1_NEG);
+    not_in_field[32..].copy_from_slice(&hex(
+        "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+    ));
+    assert_eq!(
+        ecadd::<B>(&not_in_field),
+        Err(VerifyError::CoordinateNotInField)
+    );
+
+    // G2 with the Fq2 coefficients in arkworks and snarkjs order is off the twist
+    let g2 = hex(G2);
+    let swapped = [&g2[32..64], &g2[..32], &g2[96..], &g2[64..96]].concat();
+    assert_eq!(
+        ecpairing::<B>(&[g1, swapped].concat()),
+        Err(VerifyError::PointNotOnCurve)
+    );
+}
+
+// `Zeropool` itself, whatever backs `bn`
+#[cfg(feature = "zeropool")]
+#[test]
+fn precompiles_match_eip_196_197_zeropool() {
+    use casper_groth16::bn::Zeropool;
+
+    check_backend::<Zeropool>();
+    check_invalid_points::<Zeropool>();
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn precompiles_match_eip_196_197_arkworks() {
+    use casper_groth16::bn254::Arkworks;
+
+    check_backend::<Arkworks>();
+    check_invalid_points::<Arkworks>();
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "std")]
+
+// Known-answer vectors for the bn256 precompiles in go-ethereum's `Input` / `Expected` /
+// `ExpectedError` / `Name` layout, see tests/data/precompiles. `bn256*.json` are cases from
+// go-ethereum's core/vm/testdata/precompiles. `extra-bn256*.json` are edge cases computed with
+// arkworks and `fail-bn256*.json` invalid inputs written for this crate.
+use casper_groth16::groth16::Bn254Backend;
+use casper_groth16::precompile::{ecadd, ecmul, ecpairing};
+use casper_groth16::VerifyError;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Vector {
+    input: String,
+    expected: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FailVector {
+    input: String,
+    expected_error: String,
+    name: String,
+}
+
+type Precompile = fn(&[u8]) -> Result<Vec<u8>, VerifyError>;
+
+fn hex(value: &str) -> Vec<u8> {
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn error(message: &str) -> VerifyError {
+    match message {
+        "bn256: malformed point" => VerifyError::PointNotOnCurve,
+        "bn256: point not in subgroup" => VerifyError::PointNotInSubgroup,
+        "bn256: coordinate exceeds modulus" => VerifyError::CoordinateNotInField,
+        "bad elliptic curve pairing size" => VerifyError::InvalidEncoding,
+        _ => panic!("unknown expected error {message:?}"),
+    }
+}
+
+fn run(precompile: Precompile, files: [&str; 2], failures: &str) {
+    for vectors in files {
+        let vectors: Vec<Vector> = serde_json::from_str(vectors).unwrap();
+        assert!(!vectors.is_empty());
+        for vector in vectors {
+            assert_eq!(
+                precompile(&hex(&vector.input)),
+                Ok(hex(&vector.expected)),
+                "{}",
+                vector.name
+            );
+        }
+    }
+    let failures: Vec<FailVector> = serde_json::from_str(failures).unwrap();
+    assert!(!failures.is_empty());
+    for vector in failures {
+        assert_eq!(
+            precompile(&hex(&vector.input)),
+            Err(error(&vector.expected_error)),
+            "{}",
+            vector.name
+        );
+    }
+}
+
+fn check_backend<B: Bn254Backend>() {
+    run(
+        |input| ecadd::<B>(input).map(|output| output.to_vec()),
+        [
+            include_str!("data/precompiles/bn256Add.json"),
+            include_str!("data/precompiles/extra-bn256Add.json"),
+        ],
+        include_str!("data/precompiles/fail-bn256Add.json"),
+    );
+    run(
+        |input| ecmul::<B>(input).map(|output| output.to_vec()),
+        [
+            include_str!("data/precompiles/bn256ScalarMul.json"),
+            include_str!("data/precompiles/extra-bn256ScalarMul.json"),
+        ],
+        include_str!("data/precompiles/fail-bn256ScalarMul.json"),
+    );
+    run(
+        |input| ecpairing::<B>(input).map(|output| output.to_vec()),
+        [
+            include_str!("data/precompiles/bn256Pairing.json"),
+            include_str!("data/precompiles/extra-bn256Pairing.json"),
+        ],
+        include_str!("data/precompiles/fail-bn256Pairing.json"),
+    );
+}
+
+#[cfg(feature = "zeropool")]
+#[test]
+fn precompile_vectors_zeropool() {
+    use casper_groth16::bn::Zeropool;
+
+    check_backend::<Zeropool>();
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn precompile_vectors_arkworks() {
+    use casper_groth16::bn254::Arkworks;
+
+    check_backend::<Arkworks>();
+}
+
+// `CasperHost` validates points with zeropool, so this runs without the host functions
+#[cfg(feature = "casper")]
+#[test]
+fn casper_host_validation_vectors() {
+    use casper_groth16::bn::CasperHost;
+    use casper_groth16::groth16::{swap_fq2_coefficients, G1_BYTES};
+
+    let failures: Vec<FailVector> =
+        serde_json::from_str(include_str!("data/precompiles/fail-bn256Pairing.json")).unwrap();
+    for vector in failures.iter().filter(|vector| vector.input.len() == 384) {
+        let input = hex(&vector.input);
+        let result = CasperHost::g1_from_bytes(input[..G1_BYTES].try_into().unwrap())
+            .and_then(|p| CasperHost::validate_g1(&p))
+            .and_then(|_| {
+                CasperHost::g2_from_bytes(&swap_fq2_coefficients(
+                    input[G1_BYTES..].try_into().unwrap(),
+                ))
+            })
+            .and_then(|p| CasperHost::validate_g2(&p));
+        assert_eq!(
+            result,
+            Err(error(&vector.expected_error)),
+            "{}",
+            vector.name
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg(all(feature = "std", any(feature = "ark", not(feature = "casper"))))]
+
+mod common;
+
+use casper_groth16::snarkjs::SnarkjsPublicSignals;
+use common::snarkjs;
+
+fn wrong_signals(signals: &SnarkjsPublicSignals) -> SnarkjsPublicSignals {
+    let mut signals = signals.clone();
+    signals.0[1] = "4".to_string();
+    signals
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn prepared_key_verifies_bn() {
+    use casper_groth16::bn;
+
+    let (vk, proof, signals) = snarkjs::public_inputs();
+    let vk: bn::VerifyingKey = (&vk).try_into().unwrap();
+    let proof: bn::Proof = (&proof).try_into().unwrap();
+    let pvk = bn::PreparedVerifyingKey::new(vk.clone()).unwrap();
+    assert_eq!(pvk.vk(), &vk);
+
+    let inputs: bn::PublicInputs = (&signals).try_into().unwrap();
+    assert_eq!(bn::verify_prepared(&pvk, &proof, &inputs), Ok(true));
+    assert_eq!(bn::verify(&vk, &proof, &inputs), Ok(true));
+
+    let inputs: bn::PublicInputs = (&wrong_signals(&signals)).try_into().unwrap();
+    assert_eq!(bn::verify_prepared(&pvk, &proof, &inputs), Ok(false));
+    assert_eq!(bn::verify(&vk, &proof, &inputs), Ok(false));
+}
+
+#[cfg(feature = "ark")]
+#[test]
+fn prepared_key_verifies_bn254() {
+    use casper_groth16::bn254;
+
+    let (vk, proof, signals) = snarkjs::public_inputs();
+    let vk: bn254::VerifyingKey = (&vk).try_into().unwrap();
+    let proof: bn254::Proof = (&proof).try_into().unwrap();
+    let pvk = bn254::PreparedVerifyingKey::new(vk.clone()).unwrap();
+    assert_eq!(pvk.vk(), &vk);
+
+    let inputs: bn254::PublicInputs = (&signals).try_into().unwrap();
+    assert_eq!(bn254::verify_prepared(&pvk, &proof, &inputs), Ok(true));
+    assert_eq!(bn254::verify(&vk, &proof, &inputs), Ok(true));
+
+    let inputs: bn254::PublicInputs = (&wrong_signals(&signals)).try_into().unwrap();
+    assert_eq!(bn254::verify_prepared(&pvk, &proof, &inputs), Ok(false));
+    assert_eq!(bn254::verify(&vk, &proof, &inputs), Ok(false));
+}
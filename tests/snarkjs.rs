@@ -0,0 +1,118 @@
+#![cfg(all(feature = "std", any(feature = "ark", not(feature = "casper"))))]
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+use casper_groth16::bn;
+#[cfg(feature = "ark")]
+use casper_groth16::bn254;
+use casper_groth16::snarkjs::{SnarkjsProof, SnarkjsPublicSignals, SnarkjsVerifyingKey};
+use casper_groth16::VerifyError;
+
+struct Fixture {
+    vk: SnarkjsVerifyingKey,
+    proof: SnarkjsProof,
+    public: SnarkjsPublicSignals,
+}
+
+fn load(vk: &str, proof: &str, public: &str) -> Fixture {
+    Fixture {
+        vk: SnarkjsVerifyingKey::from_json(vk).unwrap(),
+        proof: SnarkjsProof::from_json(proof).unwrap(),
+        public: SnarkjsPublicSignals::from_json(public).unwrap(),
+    }
+}
+
+fn multiplier2() -> Fixture {
+    load(
+        include_str!("data/multiplier2/verification_key.json"),
+        include_str!("data/multiplier2/proof.json"),
+        include_str!("data/multiplier2/public.json"),
+    )
+}
+
+fn public_inputs() -> Fixture {
+    load(
+        include_str!("data/public_inputs/verification_key.json"),
+        include_str!("data/public_inputs/proof.json"),
+        include_str!("data/public_inputs/public.json"),
+    )
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+fn verify_bn(fixture: &Fixture) -> Result<bool, VerifyError> {
+    let vk = bn::VerifyingKey::try_from(&fixture.vk)?;
+    let proof = bn::Proof::try_from(&fixture.proof)?;
+    let inputs = bn::PublicInputs::try_from(&fixture.public)?;
+    bn::verify(&vk, &proof, &inputs)
+}
+
+#[cfg(feature = "ark")]
+fn verify_bn254(fixture: &Fixture) -> Result<bool, VerifyError> {
+    let vk = bn254::VerifyingKey::try_from(&fixture.vk)?;
+    let proof = bn254::Proof::try_from(&fixture.proof)?;
+    let inputs = bn254::PublicInputs::try_from(&fixture.public)?;
+    bn254::verify(&vk, &proof, &inputs)
+}
+
+#[test]
+fn snarkjs_multiplier_2() {
+    let fixture = multiplier2();
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(verify_bn(&fixture), Ok(true));
+    #[cfg(feature = "ark")]
+    assert_eq!(verify_bn254(&fixture), Ok(true));
+}
+
+#[test]
+fn snarkjs_public_inputs() {
+    let fixture = public_inputs();
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(verify_bn(&fixture), Ok(true));
+    #[cfg(feature = "ark")]
+    assert_eq!(verify_bn254(&fixture), Ok(true));
+}
+
+#[test]
+fn snarkjs_wrong_public_signal() {
+    let mut fixture = multiplier2();
+    fixture.public = SnarkjsPublicSignals(vec!["34".to_string()]);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(verify_bn(&fixture), Ok(false));
+    #[cfg(feature = "ark")]
+    assert_eq!(verify_bn254(&fixture), Ok(false));
+}
+
+#[test]
+fn snarkjs_fq2_coefficients_are_not_swapped() {
+    let mut fixture = multiplier2();
+    fixture.proof.pi_b[0].swap(0, 1);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_ne!(verify_bn(&fixture), Ok(true));
+    #[cfg(feature = "ark")]
+    assert_ne!(verify_bn254(&fixture), Ok(true));
+}
+
+#[test]
+fn snarkjs_rejects_other_protocols() {
+    let json = include_str!("data/multiplier2/proof.json").replace("groth16", "plonk");
+    assert_eq!(
+        SnarkjsProof::from_json(&json).unwrap_err(),
+        VerifyError::Unsupported
+    );
+    assert_eq!(
+        SnarkjsProof::from_json("{}").unwrap_err(),
+        VerifyError::InvalidJson
+    );
+}
+
+#[test]
+fn snarkjs_rejects_ic_count_mismatch() {
+    let json = include_str!("data/multiplier2/verification_key.json");
+    let json = json.replace("\"nPublic\": 1", &format!("\"nPublic\": {}", usize::MAX));
+    assert_eq!(
+        SnarkjsVerifyingKey::from_json(&json).unwrap_err(),
+        VerifyError::InputCountMismatch {
+            expected: usize::MAX,
+            got: 1
+        }
+    );
+}
@@ -0,0 +1,195 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+mod common;
+
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn;
+use casper_groth16::solidity::{
+    selector, verify_proof_selector, SolidityCalldata, SoliditySignals,
+};
+use casper_groth16::{bn254, VerifyError};
+use common::snarkjs::{self, Snarkjs};
+use num_bigint::BigUint;
+
+fn calldata(fixture: &Snarkjs) -> SolidityCalldata {
+    let proof: bn254::Proof = (&fixture.1).try_into().unwrap();
+    let inputs: bn254::PublicInputs = (&fixture.2).try_into().unwrap();
+    SolidityCalldata {
+        proof: (&proof).into(),
+        public: (&inputs).try_into().unwrap(),
+    }
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+fn verify_bn(fixture: &Snarkjs, calldata: &SolidityCalldata) -> Result<bool, VerifyError> {
+    bn::Proof::try_from(&calldata.proof).and_then(|proof| {
+        bn::verify(
+            &(&fixture.0).try_into()?,
+            &proof,
+            &(&calldata.public).into(),
+        )
+    })
+}
+
+fn verify_bn254(fixture: &Snarkjs, calldata: &SolidityCalldata) -> Result<bool, VerifyError> {
+    bn254::Proof::try_from(&calldata.proof).and_then(|proof| {
+        bn254::verify(
+            &(&fixture.0).try_into()?,
+            &proof,
+            &(&calldata.public).into(),
+        )
+    })
+}
+
+// The decimal strings of snarkjs as the hex words of `generatecall`
+fn hex(decimal: &str) -> String {
+    let digits = BigUint::parse_bytes(decimal.as_bytes(), 10)
+        .unwrap()
+        .to_str_radix(16);
+    format!("\"0x{digits:0>64}\"")
+}
+
+#[test]
+fn selectors_are_keccak_prefixes() {
+    assert_eq!(
+        selector("transfer(address,uint256)"),
+        [0xa9, 0x05, 0x9c, 0xbb]
+    );
+    assert_eq!(
+        verify_proof_selector(3),
+        selector("verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[3])")
+    );
+    assert_ne!(verify_proof_selector(3), verify_proof_selector(4));
+}
+
+#[test]
+fn calldata_round_trips_and_verifies() {
+    let fixture = snarkjs::public_inputs();
+    let calldata = calldata(&fixture);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(verify_bn(&fixture, &calldata), Ok(true));
+    assert_eq!(verify_bn254(&fixture, &calldata), Ok(true));
+
+    let bytes = calldata.to_bytes();
+    assert_eq!(bytes.len(), 4 + 32 * (8 + 3));
+    assert_eq!(bytes[..4], verify_proof_selector(3));
+    assert_eq!(SolidityCalldata::from_bytes(&bytes), Ok(calldata.clone()));
+
+    let text = calldata.to_generatecall();
+    assert_eq!(
+        SolidityCalldata::from_generatecall(&text),
+        Ok(calldata.clone())
+    );
+
+    // the `bn` conversions agree with the `bn254` ones
+    #[cfg(feature = "zeropool")]
+    {
+        let proof: bn::Proof = (&calldata.proof).try_into().unwrap();
+        let inputs: bn::PublicInputs = (&calldata.public).into();
+        assert_eq!(
+            SolidityCalldata {
+                proof: (&proof).into(),
+                public: (&inputs).into(),
+            },
+            calldata
+        );
+    }
+}
+
+#[test]
+fn generatecall_puts_the_imaginary_part_first() {
+    let fixture = snarkjs::public_inputs();
+    let (_, proof, public) = &fixture;
+    let (a, b, c) = (&proof.pi_a, &proof.pi_b, &proof.pi_c);
+    let public: Vec<String> = public.0.iter().map(|s| hex(s)).collect();
+    // as printed by `snarkjs zkey export soliditycalldata`
+    let text = format!(
+        "[{}, {}],[[{}, {}],[{}, {}]],[{}, {}],[{}]\n",
+        hex(&a[0]),
+        hex(&a[1]),
+        hex(&b[0][1]),
+        hex(&b[0][0]),
+        hex(&b[1][1]),
+        hex(&b[1][0]),
+        hex(&c[0]),
+        hex(&c[1]),
+        public.join(",")
+    );
+    let calldata = SolidityCalldata::from_generatecall(&text).unwrap();
+    assert_eq!(calldata, self::calldata(&fixture));
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(verify_bn(&fixture, &calldata), Ok(true));
+    assert_eq!(verify_bn254(&fixture, &calldata), Ok(true));
+
+    let mut swapped = calldata.clone();
+    swapped.proof.b[0].swap(0, 1);
+    swapped.proof.b[1].swap(0, 1);
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_ne!(verify_bn(&fixture, &swapped), Ok(true));
+    assert_ne!(verify_bn254(&fixture, &swapped), Ok(true));
+}
+
+#[test]
+fn calldata_without_public_signals() {
+    let mut calldata = calldata(&snarkjs::public_inputs());
+    calldata.public = SoliditySignals::default();
+    let bytes = calldata.to_bytes();
+    assert_eq!(bytes[..4], verify_proof_selector(0));
+    assert_eq!(SolidityCalldata::from_bytes(&bytes), Ok(calldata.clone()));
+    let text = calldata.to_generatecall();
+    assert!(text.ends_with(",[]"));
+    assert_eq!(SolidityCalldata::from_generatecall(&text), Ok(calldata));
+}
+
+#[test]
+fn malformed_calldata_is_rejected() {
+    let invalid = Err(VerifyError::InvalidEncoding);
+    let bytes = calldata(&snarkjs::public_inputs()).to_bytes();
+
+    assert_eq!(
+        SolidityCalldata::from_bytes(&bytes[..bytes.len() - 1]),
+        invalid
+    );
+    // a whole word less changes the selector the length implies
+    assert_eq!(
+        SolidityCalldata::from_bytes(&bytes[..bytes.len() - 32]),
+        invalid
+    );
+    assert_eq!(SolidityCalldata::from_bytes(&bytes[..4 + 7 * 32]), invalid);
+    let mut selector = bytes.clone();
+    selector[0] ^= 1;
+    assert_eq!(SolidityCalldata::from_bytes(&selector), invalid);
+
+    let text = calldata(&snarkjs::public_inputs()).to_generatecall();
+    for malformed in [
+        text.replacen("0x", "", 1),
+        text.replacen("0x", "0xg", 1),
+        text.replacen("0x", "0x1", 1),
+        text.replacen('"', "", 1),
+        text.replacen("[[", "[", 1),
+        text.replacen("],[", "],[0,", 1),
+        format!("{text},[]"),
+        "[]".to_string(),
+    ] {
+        assert_eq!(
+            SolidityCalldata::from_generatecall(&malformed),
+            invalid,
+            "{malformed}"
+        );
+    }
+
+    // short words are zero padded
+    assert_eq!(
+        SolidityCalldata::from_generatecall(&text.replace(&hex("33"), "\"0x21\"")),
+        Ok(calldata(&snarkjs::public_inputs()))
+    );
+}
+
+#[test]
+fn wide_inputs_do_not_fit_a_word() {
+    let inputs = bn254::PublicInputs::from(vec![BigUint::from(1u8) << 256]);
+    assert_eq!(
+        SoliditySignals::try_from(&inputs),
+        Err(VerifyError::ScalarOutOfRange)
+    );
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+mod common;
+
+use ark_bn254::{g2, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ff::{Field, One};
+use casper_groth16::types::VerifyOptions;
+use casper_groth16::{bn254, VerifyError};
+use common::snarkjs;
+
+fn fixture() -> (bn254::VerifyingKey, bn254::Proof, bn254::PublicInputs) {
+    snarkjs::convert(&snarkjs::multiplier2())
+}
+
+// A point on the twist y^2 = x^3 + b', with overwhelming probability outside the r-torsion
+fn g2_outside_subgroup() -> G2Affine {
+    let mut x = Fq2::one();
+    loop {
+        let rhs = x * x * x + g2::Config::COEFF_B;
+        if let Some(y) = rhs.sqrt() {
+            let p = G2Affine::new_unchecked(x, y);
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return p;
+            }
+        }
+        x += Fq2::one();
+    }
+}
+
+#[test]
+fn off_curve_proof_points_are_rejected() {
+    let (vk, proof, inputs) = fixture();
+
+    let mut tampered = proof;
+    tampered.a = G1Affine::new_unchecked(proof.a.x, proof.a.y + Fq::one());
+    assert_eq!(
+        bn254::verify(&vk, &tampered, &inputs),
+        Err(VerifyError::PointNotOnCurve)
+    );
+
+    let mut tampered = proof;
+    tampered.b = G2Affine::new_unchecked(proof.b.x, proof.b.y + Fq2::one());
+    assert_eq!(
+        bn254::verify(&vk, &tampered, &inputs),
+        Err(VerifyError::PointNotOnCurve)
+    );
+
+    let mut tampered = proof;
+    tampered.c = G1Affine::new_unchecked(proof.c.y, proof.c.x);
+    assert_eq!(
+        bn254::verify(&vk, &tampered, &inputs),
+        Err(VerifyError::PointNotOnCurve)
+    );
+}
+
+#[test]
+fn g2_outside_subgroup_is_rejected() {
+    let p = g2_outside_subgroup();
+    assert!(p.is_on_curve());
+    assert_eq!(
+        bn254::g2_from_coords(p.x, p.y),
+        Err(VerifyError::PointNotInSubgroup)
+    );
+
+    let (vk, mut proof, inputs) = fixture();
+    proof.b = p;
+    assert_eq!(
+        bn254::verify(&vk, &proof, &inputs),
+        Err(VerifyError::PointNotInSubgroup)
+    );
+}
+
+#[cfg(all(feature = "zeropool", not(feature = "casper")))]
+#[test]
+fn g2_outside_subgroup_is_rejected_bn() {
+    use casper_groth16::bn;
+    use casper_types::U256;
+
+    let p = g2_outside_subgroup();
+    let u256 = |f: Fq| U256::from_dec_str(&f.to_string()).unwrap();
+    let result = bn::alt_bn128_pairing(vec![(
+        U256::one(),
+        U256::from(2),
+        u256(p.x.c0),
+        u256(p.x.c1),
+        u256(p.y.c0),
+        u256(p.y.c1),
+    )]);
+    assert_eq!(result, Err(VerifyError::PointNotInSubgroup));
+}
+
+#[test]
+fn vk_validation_can_be_skipped_for_trusted_keys() {
+    let (mut vk, proof, inputs) = fixture();
+    vk.gamma2 = g2_outside_subgroup();
+    assert_eq!(
+        bn254::verify(&vk, &proof, &inputs),
+        Err(VerifyError::PointNotInSubgroup)
+    );
+    assert_eq!(
+        bn254::verify_with_options(&vk, &proof, &inputs, &VerifyOptions::trusted_vk()),
+        Ok(false)
+    );
+
+    let (vk, proof, inputs) = fixture();
+    assert_eq!(
+        bn254::verify_with_options(&vk, &proof, &inputs, &VerifyOptions::trusted_vk()),
+        Ok(true)
+    );
+}
@@ -0,0 +1,289 @@
+#![cfg(all(feature = "std", feature = "ark"))]
+
+// zkeys are written here from the snarkjs fixtures, with the sections snarkjs lays out for a
+// Groth16 key. The proving key sections are stand-ins, the reader skips them.
+use ark_bn254::{Fq, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+#[cfg(feature = "zeropool")]
+use casper_groth16::bn;
+use casper_groth16::snarkjs::{SnarkjsProof, SnarkjsPublicSignals, SnarkjsVerifyingKey};
+use casper_groth16::zkey::ZkeyVerifyingKey;
+use casper_groth16::{bn254, VerifyError};
+use num_bigint::BigUint;
+
+fn fixture_vk(json: &str) -> bn254::VerifyingKey {
+    (&SnarkjsVerifyingKey::from_json(json).unwrap())
+        .try_into()
+        .unwrap()
+}
+
+// `x * 2^256 mod p` little-endian, computed on integers rather than read out of `Fq`, which keeps
+// the same form internally
+fn fq(value: &Fq) -> Vec<u8> {
+    let p = BigUint::from(Fq::MODULUS);
+    let montgomery: BigUint = (BigUint::from(value.into_bigint()) << 256) % p;
+    let mut bytes = montgomery.to_bytes_le();
+    bytes.resize(32, 0);
+    bytes
+}
+
+fn g1(p: &G1Affine) -> Vec<u8> {
+    [fq(&p.x), fq(&p.y)].concat()
+}
+
+fn g2(p: &G2Affine) -> Vec<u8> {
+    [fq(&p.x.c0), fq(&p.x.c1), fq(&p.y.c0), fq(&p.y.c1)].concat()
+}
+
+fn section(bytes: &mut Vec<u8>, section: u32, data: &[u8]) {
+    bytes.extend(section.to_le_bytes());
+    bytes.extend((data.len() as u64).to_le_bytes());
+    bytes.extend(data);
+}
+
+fn modulus(modulus: impl BigInteger) -> Vec<u8> {
+    [32u32.to_le_bytes().to_vec(), modulus.to_bytes_le()].concat()
+}
+
+fn groth16_header(vk: &bn254::VerifyingKey) -> Vec<u8> {
+    // `[beta]1` and `[delta]1` are only read by the prover, any point will do
+    let unused = G1Affine::generator();
+    let n_public = vk.ic.len() as u32 - 1;
+    let mut bytes = modulus(Fq::MODULUS);
+    bytes.extend(modulus(ark_bn254::Fr::MODULUS));
+    bytes.extend((n_public + 3).to_le_bytes());
+    bytes.extend(n_public.to_le_bytes());
+    bytes.extend(4u32.to_le_bytes());
+    bytes.extend(g1(&vk.alpha1));
+    bytes.extend(g1(&unused));
+    bytes.extend(g2(&vk.beta2));
+    bytes.extend(g2(&vk.gamma2));
+    bytes.extend(g1(&unused));
+    bytes.extend(g2(&vk.delta2));
+    bytes
+}
+
+fn ic(vk: &bn254::VerifyingKey) -> Vec<u8> {
+    vk.ic.iter().flat_map(g1).collect()
+}
+
+// snarkjs writes sections in this order, with the IC points right after the Groth16 header
+fn zkey_bytes(sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = b"zkey".to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (id, data) in sections {
+        section(&mut bytes, *id, data);
+    }
+    bytes
+}
+
+fn sections(vk: &bn254::VerifyingKey) -> Vec<(u32, Vec<u8>)> {
+    vec![
+        (1, 1u32.to_le_bytes().to_vec()),
+        (2, groth16_header(vk)),
+        (3, ic(vk)),
+        (4, vec![0; 12]),
+        (5, g1(&G1Affine::generator())),
+    ]
+}
+
+#[test]
+fn zkey_matches_verification_key_json() {
+    for (json, proof, public) in [
+        (
+            include_str!("data/multiplier2/verification_key.json"),
+            include_str!("data/multiplier2/proof.json"),
+            include_str!("data/multiplier2/public.json"),
+        ),
+        (
+            include_str!("data/public_inputs/verification_key.json"),
+            include_str!("data/public_inputs/proof.json"),
+            include_str!("data/public_inputs/public.json"),
+        ),
+    ] {
+        let expected = fixture_vk(json);
+        let zkey = ZkeyVerifyingKey::from_bytes(&zkey_bytes(&sections(&expected))).unwrap();
+        assert_eq!(zkey.n_public as usize, expected.ic.len() - 1);
+
+        let vk = bn254::VerifyingKey::try_from(&zkey).unwrap();
+        assert_eq!(vk, expected);
+        let proof = SnarkjsProof::from_json(proof).unwrap();
+        let public = SnarkjsPublicSignals::from_json(public).unwrap();
+        assert_eq!(
+            bn254::verify(
+                &vk,
+                &(&proof).try_into().unwrap(),
+                &(&public).try_into().unwrap()
+            ),
+            Ok(true)
+        );
+
+        #[cfg(feature = "zeropool")]
+        {
+            let vk = bn::VerifyingKey::try_from(&zkey).unwrap();
+            let json: bn::VerifyingKey = (&SnarkjsVerifyingKey::from_json(json).unwrap())
+                .try_into()
+                .unwrap();
+            assert_eq!(vk, json);
+            #[cfg(not(feature = "casper"))]
+            assert_eq!(
+                bn::verify(
+                    &vk,
+                    &(&proof).try_into().unwrap(),
+                    &(&public).try_into().unwrap()
+                ),
+                Ok(true)
+            );
+        }
+    }
+}
+
+#[test]
+fn zkey_elements_are_in_montgomery_form() {
+    // the generator (1, 2) as `R mod p` and `2R mod p`, little-endian
+    let hex = "9d0d8fc58d435dd33d0bc7f528eb780a2c4679786fa36e662fdf079ac1770a0e\
+               3a1b1e8b1b87baa67b168eeb51d6f114588cf2f0de46ddcc5ebe0f3483ef141c";
+    let generator: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    assert_eq!(g1(&G1Affine::generator()), generator);
+
+    let vk = fixture_vk(include_str!("data/multiplier2/verification_key.json"));
+    let mut sections = sections(&vk);
+    sections[1].1[84..148].copy_from_slice(&generator);
+    let zkey = ZkeyVerifyingKey::from_bytes(&zkey_bytes(&sections)).unwrap();
+    assert_eq!(
+        bn254::VerifyingKey::try_from(&zkey).unwrap().alpha1,
+        G1Affine::generator()
+    );
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::VerifyingKey::try_from(&zkey).unwrap().alpha1,
+        bn::G1Point::new(1.into(), 2.into())
+    );
+}
+
+#[test]
+fn zkey_sections_may_come_in_any_order() {
+    let vk = fixture_vk(include_str!("data/public_inputs/verification_key.json"));
+    let mut sections = sections(&vk);
+    sections.reverse();
+    let zkey = ZkeyVerifyingKey::from_bytes(&zkey_bytes(&sections)).unwrap();
+    assert_eq!(bn254::VerifyingKey::try_from(&zkey), Ok(vk));
+}
+
+#[test]
+fn zkey_rejects_other_protocols_and_curves() {
+    let vk = fixture_vk(include_str!("data/multiplier2/verification_key.json"));
+    let unsupported = Err(VerifyError::Unsupported);
+
+    // plonk
+    let mut plonk = sections(&vk);
+    plonk[0].1 = 2u32.to_le_bytes().to_vec();
+    assert_eq!(
+        ZkeyVerifyingKey::from_bytes(&zkey_bytes(&plonk)),
+        unsupported
+    );
+
+    // BLS12-381 has a 48 byte base field
+    let mut bls = sections(&vk);
+    bls[1].1 = [
+        48u32.to_le_bytes().to_vec(),
+        vec![0xff; 48],
+        bls[1].1[36..].to_vec(),
+    ]
+    .concat();
+    assert_eq!(ZkeyVerifyingKey::from_bytes(&zkey_bytes(&bls)), unsupported);
+
+    let mut version = zkey_bytes(&sections(&vk));
+    version[4] = 2;
+    assert_eq!(ZkeyVerifyingKey::from_bytes(&version), unsupported);
+}
+
+#[test]
+fn zkey_rejects_malformed_files() {
+    let vk = fixture_vk(include_str!("data/multiplier2/verification_key.json"));
+    let invalid = Err(VerifyError::InvalidEncoding);
+    let bytes = zkey_bytes(&sections(&vk));
+
+    assert_eq!(ZkeyVerifyingKey::from_bytes(b""), invalid);
+    let mut magic = bytes.clone();
+    magic[..4].copy_from_slice(b"wtns");
+    assert_eq!(ZkeyVerifyingKey::from_bytes(&magic), invalid);
+    assert_eq!(
+        ZkeyVerifyingKey::from_bytes(&bytes[..bytes.len() - 1]),
+        invalid
+    );
+    assert_eq!(
+        ZkeyVerifyingKey::from_bytes(&[&bytes[..], &[0]].concat()),
+        invalid
+    );
+
+    let mut missing = sections(&vk);
+    missing.remove(2);
+    assert_eq!(ZkeyVerifyingKey::from_bytes(&zkey_bytes(&missing)), invalid);
+    let mut duplicate = sections(&vk);
+    duplicate.push(duplicate[2].clone());
+    assert_eq!(
+        ZkeyVerifyingKey::from_bytes(&zkey_bytes(&duplicate)),
+        invalid
+    );
+
+    let mut ic = sections(&vk);
+    ic[2].1.pop();
+    assert_eq!(ZkeyVerifyingKey::from_bytes(&zkey_bytes(&ic)), invalid);
+    ic[2].1.truncate(64);
+    assert_eq!(
+        ZkeyVerifyingKey::from_bytes(&zkey_bytes(&ic)),
+        Err(VerifyError::InputCountMismatch {
+            expected: 1,
+            got: 0
+        })
+    );
+}
+
+#[test]
+fn zkey_rejects_coordinates_outside_the_field() {
+    let vk = fixture_vk(include_str!("data/multiplier2/verification_key.json"));
+    let mut zkey = ZkeyVerifyingKey::from_bytes(&zkey_bytes(&sections(&vk))).unwrap();
+    zkey.alpha1[0].copy_from_slice(&Fq::MODULUS.to_bytes_le());
+    assert_eq!(
+        bn254::VerifyingKey::try_from(&zkey),
+        Err(VerifyError::CoordinateNotInField)
+    );
+    #[cfg(feature = "zeropool")]
+    assert_eq!(
+        bn::VerifyingKey::try_from(&zkey),
+        Err(VerifyError::CoordinateNotInField)
+    );
+
+    // in range but read as the plain value, so off the curve once the key is validated
+    let mut plain = ZkeyVerifyingKey::from_bytes(&zkey_bytes(&sections(&vk))).unwrap();
+    plain.ic[0] = [
+        vk.ic[0].x.into_bigint().to_bytes_le().try_into().unwrap(),
+        vk.ic[0].y.into_bigint().to_bytes_le().try_into().unwrap(),
+    ];
+    let proof = SnarkjsProof::from_json(include_str!("data/multiplier2/proof.json")).unwrap();
+    let public =
+        SnarkjsPublicSignals::from_json(include_str!("data/multiplier2/public.json")).unwrap();
+    assert_eq!(
+        bn254::verify(
+            &(&plain).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&public).try_into().unwrap()
+        ),
+        Err(VerifyError::PointNotOnCurve)
+    );
+    #[cfg(all(feature = "zeropool", not(feature = "casper")))]
+    assert_eq!(
+        bn::verify(
+            &(&plain).try_into().unwrap(),
+            &(&proof).try_into().unwrap(),
+            &(&public).try_into().unwrap()
+        ),
+        Err(VerifyError::PointNotOnCurve)
+    );
+}